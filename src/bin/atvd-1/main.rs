@@ -1,15 +1,21 @@
-use std::thread;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
 const RUNS: usize = 5;
+const JOIN_DEADLINE: Duration = Duration::from_millis(20);
+const SIMULATED_DELAYS_MS: [u64; 4] = [5, 40, 10, 60];
 
 fn main() {
-	assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful");
+	const { assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful") };
 
 	println!("Atividade 1 — Uma thread \"hello\"");
 	println!("Total de execucoes: {} ({} usadas na media apos descartar o aquecimento)", RUNS, RUNS - 1);
 
 	let (parallel_avg, parallel_times, parallel_outputs) = measure_runs(|run| hello_thread(run == 0));
+	let (parked_avg, parked_times, parked_outputs) = measure_runs(|run| hello_thread_parked(run == 0));
 	let (sequential_avg, sequential_times, sequential_outputs) = measure_runs(|run| sequential_hello(run == 0));
 
 	let is_correct = parallel_outputs
@@ -17,17 +23,124 @@ fn main() {
 		.skip(1)
 		.zip(sequential_outputs.iter().skip(1))
 		.all(|(parallel, sequential)| parallel == sequential);
+	let parked_correct = parked_outputs
+		.iter()
+		.skip(1)
+		.zip(sequential_outputs.iter().skip(1))
+		.all(|(parked, sequential)| parked == sequential);
 
 	println!("\nTempos paralelos (ms):");
 	log_durations(&parallel_times);
 	println!("Tempo medio paralelo (ms): {:.6}", parallel_avg * 1_000.0);
 
+	println!("\nTempos com handshake park/unpark (ms):");
+	log_durations(&parked_times);
+	println!("Tempo medio park/unpark (ms): {:.6}", parked_avg * 1_000.0);
+
 	println!("\nTempos sequenciais (ms):");
 	log_durations(&sequential_times);
 	println!("Tempo medio sequencial (ms): {:.6}", sequential_avg * 1_000.0);
 
 	println!("\nCorretude apos aquecimento: {}", if is_correct { "OK" } else { "FALHOU" });
+	println!("Corretude park/unpark apos aquecimento: {}", if parked_correct { "OK" } else { "FALHOU" });
 	println!("Ciclo de vida: main cria a thread, inicia com start e aguarda conclusao via join, recebendo a mensagem.");
+
+	report_timed_joins();
+}
+
+// Join com prazo: a thread envia o resultado por um canal de conclusao e main espera com recv_timeout.
+// Se o canal fechar sem mensagem, a thread entrou em panico.
+struct TimedJoin<T> {
+	handle: JoinHandle<()>,
+	result_rx: mpsc::Receiver<T>,
+}
+
+enum TimedJoinError<T> {
+	// Prazo expirado: a thread continua rodando e pode ser aguardada de novo.
+	TimedOut(TimedJoin<T>),
+	Panicked,
+}
+
+impl<T: Send + 'static> TimedJoin<T> {
+	fn spawn<F>(job: F) -> Self
+	where
+		F: FnOnce() -> T + Send + 'static,
+	{
+		let (result_tx, result_rx) = mpsc::channel();
+		let handle = thread::spawn(move || {
+			// Receptor descartado significa que ninguem aguarda mais o resultado.
+			let _ = result_tx.send(job());
+		});
+		Self { handle, result_rx }
+	}
+
+	fn join_timeout(self, timeout: Duration) -> Result<T, TimedJoinError<T>> {
+		match self.result_rx.recv_timeout(timeout) {
+			Ok(value) => {
+				self.handle.join().expect("Thread panicked after sending result");
+				Ok(value)
+			}
+			Err(RecvTimeoutError::Timeout) => Err(TimedJoinError::TimedOut(self)),
+			Err(RecvTimeoutError::Disconnected) => {
+				let _ = self.handle.join();
+				Err(TimedJoinError::Panicked)
+			}
+		}
+	}
+
+	fn join(self) -> Result<T, TimedJoinError<T>> {
+		match self.result_rx.recv() {
+			Ok(value) => {
+				self.handle.join().expect("Thread panicked after sending result");
+				Ok(value)
+			}
+			Err(_) => {
+				let _ = self.handle.join();
+				Err(TimedJoinError::Panicked)
+			}
+		}
+	}
+}
+
+fn report_timed_joins() {
+	println!("\nJoin com prazo de {} ms:", JOIN_DEADLINE.as_millis());
+
+	let mut timed_out = Vec::new();
+	for (attempt, &delay_ms) in SIMULATED_DELAYS_MS.iter().enumerate() {
+		let timed = TimedJoin::spawn(move || {
+			// Atraso simulado: algumas tentativas ultrapassam o prazo de proposito.
+			thread::sleep(Duration::from_millis(delay_ms));
+			String::from("Hello from thread!")
+		});
+
+		let start = Instant::now();
+		match timed.join_timeout(JOIN_DEADLINE) {
+			Ok(message) => println!(
+				"  Tentativa {} (atraso {} ms): concluiu em {:.3} ms -> {}",
+				attempt + 1,
+				delay_ms,
+				start.elapsed().as_secs_f64() * 1_000.0,
+				message
+			),
+			Err(TimedJoinError::TimedOut(pending)) => {
+				timed_out.push(attempt + 1);
+				println!(
+					"  Tentativa {} (atraso {} ms): prazo expirado apos {:.3} ms",
+					attempt + 1,
+					delay_ms,
+					start.elapsed().as_secs_f64() * 1_000.0
+				);
+				// Aguarda sem prazo para nao deixar a thread solta entre as tentativas.
+				match pending.join() {
+					Ok(_) => println!("    Thread atrasada terminou apos o prazo"),
+					Err(_) => println!("    Thread atrasada entrou em panico"),
+				}
+			}
+			Err(TimedJoinError::Panicked) => println!("  Tentativa {}: thread entrou em panico", attempt + 1),
+		}
+	}
+
+	println!("Tentativas que excederam o prazo: {:?}", timed_out);
 }
 
 fn measure_runs<F>(mut job: F) -> (f64, Vec<Duration>, Vec<String>)
@@ -79,6 +192,43 @@ fn hello_thread(should_print: bool) -> String {
 		.expect("Thread panicked during execution")
 }
 
+fn hello_thread_parked(should_print: bool) -> String {
+	let main_thread = thread::current();
+	let started = Arc::new(AtomicBool::new(false));
+	let finished = Arc::new(AtomicBool::new(false));
+	let slot = Arc::new(Mutex::new(None));
+
+	let handle = {
+		let started = Arc::clone(&started);
+		let finished = Arc::clone(&finished);
+		let slot = Arc::clone(&slot);
+		thread::spawn(move || {
+			// park pode acordar espuriamente, por isso a condicao e verificada em laco.
+			while !started.load(Ordering::Acquire) {
+				thread::park();
+			}
+			let message = String::from("Hello from thread!");
+			if should_print {
+				println!("Thread (park/unpark): {}", message);
+			}
+			*slot.lock().expect("Mutex poisoned") = Some(message);
+			finished.store(true, Ordering::Release);
+			main_thread.unpark();
+		})
+	};
+
+	// Handshake: main libera a thread e estaciona ate receber o sinal de conclusao.
+	started.store(true, Ordering::Release);
+	handle.thread().unpark();
+	while !finished.load(Ordering::Acquire) {
+		thread::park();
+	}
+
+	handle.join().expect("Thread panicked during execution");
+	let message = slot.lock().expect("Mutex poisoned").take();
+	message.expect("Thread finished without a message")
+}
+
 fn sequential_hello(should_print: bool) -> String {
 	let message = String::from("Hello from thread!");
 	if should_print {
@@ -10,7 +10,7 @@ const DEFAULT_SAMPLES_PER_THREAD: usize = 200_000;
 const WORKLOAD_MULTIPLIERS: [usize; 3] = [1, 5, 25];
 
 fn main() {
-	assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful");
+	const { assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful") };
 
 	let base_samples = read_samples_per_thread().unwrap_or_else(|err| {
 		eprintln!("{}", err);
@@ -244,7 +244,7 @@ struct XorShift64 {
 
 impl XorShift64 {
 	fn new(seed: u64) -> Self {
-		let state = if seed == 0 { 0xA511E9B7C3D2_1234 } else { seed };
+		let state = if seed == 0 { 0xA511_E9B7_C3D2_1234 } else { seed };
 		Self { state }
 	}
 
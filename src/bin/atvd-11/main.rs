@@ -9,7 +9,7 @@ const BLOCK_SIZE: usize = 1_000;
 const THREAD_POOL_SIZES: [usize; 3] = [2, 4, 8];
 
 fn main() {
-	assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful");
+	const { assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful") };
 
 	println!("Atividade 11 — Pool de threads (executors)");
 	println!("Tarefas: {} blocos de {} elementos", TASK_COUNT, BLOCK_SIZE);
@@ -11,7 +11,7 @@ const OPS_PER_WRITER: usize = 3_000;
 const ACCOUNT_KEYS: usize = 64;
 
 fn main() {
-	assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful");
+	const { assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful") };
 
 	println!("Atividade 12 — Leitores e Escritores");
 	println!(
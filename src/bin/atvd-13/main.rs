@@ -0,0 +1,389 @@
+mod wait_for;
+
+use std::env;
+use std::hint;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, TryLockError};
+use std::thread;
+use std::time::{Duration, Instant};
+use wait_for::{describe_cycle, TrackedMutex, WaitEdge, WaitForGraph};
+
+const RUNS: usize = 5;
+const DEFAULT_THREADS: usize = 4;
+const ACCOUNT_COUNT: usize = 8;
+const INITIAL_BALANCE: i64 = 1_000;
+const TRANSFERS_PER_THREAD: usize = 20_000;
+// A cada CONTENTION_INTERVAL transferencias a thread cede o processador segurando a primeira trava.
+const CONTENTION_INTERVAL: usize = 64;
+const WATCHDOG_POLL: Duration = Duration::from_millis(50);
+const WATCHDOG_STALLED_POLLS: usize = 6;
+const MAX_BACKOFF: u32 = 1 << 10;
+
+fn main() {
+	const { assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful") };
+
+	let thread_count = read_thread_count().unwrap_or_else(|err| {
+		eprintln!("{}", err);
+		std::process::exit(1);
+	});
+
+	assert!(thread_count >= 2, "Deadlock exige pelo menos duas threads");
+
+	let expected_total = INITIAL_BALANCE * ACCOUNT_COUNT as i64;
+
+	println!("Atividade 13 — Deadlock em transferencias entre contas");
+	println!(
+		"Config: {} threads, {} contas, {} transferencias/thread; saldo total esperado = {}",
+		thread_count,
+		ACCOUNT_COUNT,
+		TRANSFERS_PER_THREAD,
+		expected_total
+	);
+	println!("Total de execucoes temporizadas: {} ({} entram na media)", RUNS, RUNS - 1);
+
+	println!("\n=== Ordem inconsistente com std::sync::Mutex ===");
+	println!("Cada transferencia trava a conta de origem e depois a de destino; duas threads em sentidos opostos se bloqueiam.");
+	report_naive_deadlock(thread_count);
+
+	let strategies: [(&str, Strategy); 4] = [
+		("Ordem global", Strategy::GlobalOrder),
+		("try_lock + backoff", Strategy::TryLockBackoff),
+		("Grafo + ordem global", Strategy::TrackedOrdered),
+		("Grafo + deteccao", Strategy::TrackedDetection),
+	];
+
+	let mut rows = Vec::new();
+	let mut sample_cycle = None;
+	for (label, strategy) in strategies {
+		let (avg, durations, runs) = measure_runs(|_| run_transfers(strategy, thread_count));
+		println!("\nTempos {} (ms):", label);
+		log_durations(&durations);
+
+		let measured = &runs[1..];
+		if sample_cycle.is_none() {
+			sample_cycle = runs.iter().find_map(|run| run.first_cycle.clone());
+		}
+		rows.push(SummaryRow {
+			label,
+			avg_seconds: avg,
+			retries: measured.iter().map(|run| run.retries).sum::<usize>() / measured.len(),
+			cycles: measured.iter().map(|run| run.cycles).sum::<usize>() / measured.len(),
+			conserved: runs.iter().all(|run| run.total == expected_total),
+		});
+	}
+
+	let transfers = (thread_count * TRANSFERS_PER_THREAD) as f64;
+	println!("\nTabela de vazao (medias sem aquecimento):");
+	println!("Estrategia           | Tempo (ms) | Transf./s    | Repeticoes | Ciclos | Saldo conservado");
+	for row in &rows {
+		println!(
+			"{:<20} | {:>10.3} | {:>12.0} | {:>10} | {:>6} | {}",
+			row.label,
+			row.avg_seconds * 1_000.0,
+			transfers / row.avg_seconds,
+			row.retries,
+			row.cycles,
+			if row.conserved { "sim" } else { "NAO" }
+		);
+	}
+
+	match sample_cycle {
+		Some(cycle) => println!("\nExemplo de ciclo detectado em tempo de execucao: {}", describe_cycle(&cycle)),
+		None => println!("\nNenhum ciclo chegou a se formar nas execucoes com deteccao."),
+	}
+
+	println!(
+		"\nExplicacao: deadlock exige posse-e-espera, ausencia de preempcao, exclusao mutua e espera circular. \
+	A ordem global elimina a espera circular (todas travam a menor conta primeiro) sem custo extra; \
+	try_lock com backoff elimina a posse-e-espera, mas paga repeticoes quando a segunda conta esta ocupada; \
+	o grafo de espera deixa o ciclo se formar e escolhe como vitima a thread que o fecharia, \
+	ao custo de serializar toda aquisicao em um grafo global."
+	);
+}
+
+#[derive(Clone, Copy)]
+enum Strategy {
+	GlobalOrder,
+	TryLockBackoff,
+	TrackedOrdered,
+	TrackedDetection,
+}
+
+struct TransferRun {
+	total: i64,
+	retries: usize,
+	cycles: usize,
+	first_cycle: Option<Vec<WaitEdge>>,
+}
+
+struct SummaryRow {
+	label: &'static str,
+	avg_seconds: f64,
+	retries: usize,
+	cycles: usize,
+	conserved: bool,
+}
+
+fn read_thread_count() -> Result<usize, String> {
+	match env::args().nth(1) {
+		Some(arg) => arg
+			.parse::<usize>()
+			.map_err(|_| format!("Argumento invalido para numero de threads: {}", arg)),
+		None => Ok(DEFAULT_THREADS),
+	}
+}
+
+fn transfer_route(thread_id: usize, iter: usize) -> (usize, usize, i64) {
+	// Rotas variadas e sem ordem fixa: a mesma dupla de contas aparece nos dois sentidos.
+	let from = (thread_id * 3 + iter) % ACCOUNT_COUNT;
+	let to = (from + 1 + iter % (ACCOUNT_COUNT - 1)) % ACCOUNT_COUNT;
+	let amount = (iter % 7 + 1) as i64;
+	(from, to, amount)
+}
+
+fn new_accounts() -> Arc<Vec<Mutex<i64>>> {
+	Arc::new((0..ACCOUNT_COUNT).map(|_| Mutex::new(INITIAL_BALANCE)).collect())
+}
+
+fn report_naive_deadlock(thread_count: usize) {
+	let accounts = new_accounts();
+	let progress = Arc::new(AtomicUsize::new(0));
+
+	let handles: Vec<_> = (0..thread_count)
+		.map(|thread_id| {
+			let accounts = Arc::clone(&accounts);
+			let progress = Arc::clone(&progress);
+			thread::spawn(move || {
+				for iter in 0..TRANSFERS_PER_THREAD {
+					let (from, to, amount) = transfer_route(thread_id, iter);
+					let mut from_guard = accounts[from].lock().expect("Mutex poisoned");
+					// Janela larga de posse-e-espera: quase garante o ciclo.
+					thread::yield_now();
+					let mut to_guard = accounts[to].lock().expect("Mutex poisoned");
+					*from_guard -= amount;
+					*to_guard += amount;
+					progress.fetch_add(1, Ordering::Relaxed);
+				}
+			})
+		})
+		.collect();
+
+	// Vigia: sem progresso por varias amostras seguidas, as threads estao presas umas nas outras.
+	let mut last_progress = 0;
+	let mut stalled_polls = 0;
+	while stalled_polls < WATCHDOG_STALLED_POLLS {
+		if handles.iter().all(|handle| handle.is_finished()) {
+			for handle in handles {
+				handle.join().expect("Thread panicked during execution");
+			}
+			println!("Sem deadlock nesta execucao: {} transferencias concluidas.", progress.load(Ordering::Relaxed));
+			return;
+		}
+		thread::sleep(WATCHDOG_POLL);
+		let current = progress.load(Ordering::Relaxed);
+		if current == last_progress {
+			stalled_polls += 1;
+		} else {
+			stalled_polls = 0;
+			last_progress = current;
+		}
+	}
+
+	let stuck = handles.iter().filter(|handle| !handle.is_finished()).count();
+	println!(
+		"DEADLOCK: progresso parado em {} de {} transferencias por {} ms; {} threads presas.",
+		last_progress,
+		thread_count * TRANSFERS_PER_THREAD,
+		WATCHDOG_POLL.as_millis() * WATCHDOG_STALLED_POLLS as u128,
+		stuck
+	);
+	println!("As threads presas sao abandonadas (ficam bloqueadas ate o processo terminar).");
+	// Soltar os JoinHandle desanexa as threads; o join aqui nunca retornaria.
+	drop(handles);
+}
+
+fn run_transfers(strategy: Strategy, thread_count: usize) -> TransferRun {
+	match strategy {
+		Strategy::GlobalOrder | Strategy::TryLockBackoff => run_std_transfers(strategy, thread_count),
+		Strategy::TrackedOrdered | Strategy::TrackedDetection => run_tracked_transfers(strategy, thread_count),
+	}
+}
+
+fn run_std_transfers(strategy: Strategy, thread_count: usize) -> TransferRun {
+	let accounts = new_accounts();
+
+	let handles: Vec<_> = (0..thread_count)
+		.map(|thread_id| {
+			let accounts = Arc::clone(&accounts);
+			thread::spawn(move || {
+				let mut retries = 0usize;
+				for iter in 0..TRANSFERS_PER_THREAD {
+					let (from, to, amount) = transfer_route(thread_id, iter);
+					let widen_window = iter % CONTENTION_INTERVAL == 0;
+					match strategy {
+						Strategy::GlobalOrder => {
+							// Sempre a menor conta primeiro: nenhuma thread espera "para tras".
+							let (first, second) = (from.min(to), from.max(to));
+							let mut first_guard = accounts[first].lock().expect("Mutex poisoned");
+							if widen_window {
+								thread::yield_now();
+							}
+							let mut second_guard = accounts[second].lock().expect("Mutex poisoned");
+							let (from_guard, to_guard) = if first == from {
+								(&mut first_guard, &mut second_guard)
+							} else {
+								(&mut second_guard, &mut first_guard)
+							};
+							**from_guard -= amount;
+							**to_guard += amount;
+						}
+						_ => retries += transfer_with_try_lock(&accounts, from, to, amount, widen_window),
+					}
+				}
+				retries
+			})
+		})
+		.collect();
+
+	let retries = handles
+		.into_iter()
+		.map(|handle| handle.join().expect("Thread panicked during execution"))
+		.sum();
+
+	TransferRun {
+		total: accounts.iter().map(|account| *account.lock().expect("Mutex poisoned")).sum(),
+		retries,
+		cycles: 0,
+		first_cycle: None,
+	}
+}
+
+// Trava a origem e apenas tenta o destino; se ocupado, solta tudo e recua antes de repetir.
+fn transfer_with_try_lock(accounts: &[Mutex<i64>], from: usize, to: usize, amount: i64, widen_window: bool) -> usize {
+	let mut retries = 0;
+	let mut backoff = 1;
+	loop {
+		let mut from_guard = accounts[from].lock().expect("Mutex poisoned");
+		if widen_window && retries == 0 {
+			thread::yield_now();
+		}
+		match accounts[to].try_lock() {
+			Ok(mut to_guard) => {
+				*from_guard -= amount;
+				*to_guard += amount;
+				return retries;
+			}
+			Err(TryLockError::WouldBlock) => {}
+			Err(TryLockError::Poisoned(_)) => panic!("Mutex poisoned"),
+		}
+		drop(from_guard);
+		retries += 1;
+		for _ in 0..backoff {
+			hint::spin_loop();
+		}
+		if backoff < MAX_BACKOFF {
+			backoff *= 2;
+		} else {
+			thread::yield_now();
+		}
+	}
+}
+
+fn run_tracked_transfers(strategy: Strategy, thread_count: usize) -> TransferRun {
+	let graph = Arc::new(WaitForGraph::new(ACCOUNT_COUNT, thread_count));
+	let accounts: Arc<Vec<TrackedMutex<i64>>> = Arc::new(
+		(0..ACCOUNT_COUNT)
+			.map(|id| TrackedMutex::new(id, INITIAL_BALANCE, Arc::clone(&graph)))
+			.collect(),
+	);
+
+	let handles: Vec<_> = (0..thread_count)
+		.map(|thread_id| {
+			let accounts = Arc::clone(&accounts);
+			thread::spawn(move || {
+				let mut retries = 0usize;
+				for iter in 0..TRANSFERS_PER_THREAD {
+					let (from, to, amount) = transfer_route(thread_id, iter);
+					let (first, second) = match strategy {
+						Strategy::TrackedOrdered => (from.min(to), from.max(to)),
+						_ => (from, to),
+					};
+					let widen_window = iter % CONTENTION_INTERVAL == 0;
+					let mut attempt = 0;
+					loop {
+						// Sem nenhuma trava em posse a thread nao pode fechar um ciclo.
+						let mut first_guard = accounts[first].lock(thread_id).expect("Ciclo sem travas em posse");
+						if widen_window && attempt == 0 {
+							thread::yield_now();
+						}
+						match accounts[second].lock(thread_id) {
+							Ok(mut second_guard) => {
+								let delta = if first == from { -amount } else { amount };
+								*first_guard += delta;
+								*second_guard -= delta;
+								break;
+							}
+							Err(_) => {
+								// Vitima do ciclo: solta a primeira conta para as outras threads avancarem.
+								drop(first_guard);
+								attempt += 1;
+								retries += 1;
+								thread::yield_now();
+							}
+						}
+					}
+				}
+				retries
+			})
+		})
+		.collect();
+
+	let retries = handles
+		.into_iter()
+		.map(|handle| handle.join().expect("Thread panicked during execution"))
+		.sum();
+
+	TransferRun {
+		total: accounts
+			.iter()
+			.map(|account| *account.lock(0).expect("Conta presa ao final"))
+			.sum(),
+		retries,
+		cycles: graph.detected(),
+		first_cycle: graph.first_cycle(),
+	}
+}
+
+fn measure_runs<F, T>(mut job: F) -> (f64, Vec<Duration>, Vec<T>)
+where
+	F: FnMut(usize) -> T,
+{
+	let mut durations = Vec::with_capacity(RUNS);
+	let mut outputs = Vec::with_capacity(RUNS);
+
+	for run in 0..RUNS {
+		let start = Instant::now();
+		let result = job(run);
+		let elapsed = start.elapsed();
+
+		durations.push(elapsed);
+		outputs.push(result);
+	}
+
+	// Descarte o primeiro tempo (aquecimento) para reduzir variacao do cache/JIT.
+	let avg = durations
+		.iter()
+		.skip(1)
+		.map(Duration::as_secs_f64)
+		.sum::<f64>()
+		/ (RUNS - 1) as f64;
+
+	(avg, durations, outputs)
+}
+
+fn log_durations(durations: &[Duration]) {
+	for (index, duration) in durations.iter().enumerate() {
+		println!("  Execucao {}: {:.6}", index + 1, duration.as_secs_f64() * 1_000.0);
+	}
+	println!("  Obs.: primeira execucao funciona como aquecimento.");
+}
@@ -0,0 +1,163 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+// Camada de travas instrumentada com grafo de espera (wait-for). Cada trava tem um dono e cada
+// thread espera no maximo uma trava; a aresta T -> dono(L) so e criada depois de verificar que
+// seguir os donos a partir de L nao volta para T. Se voltar, ha ciclo: quem fecharia o ciclo
+// recebe `Deadlock` em vez de dormir, e deve soltar o que tem para os demais seguirem.
+pub struct WaitForGraph {
+	state: Mutex<GraphState>,
+	released: Condvar,
+}
+
+struct GraphState {
+	owner: Vec<Option<usize>>,
+	waiting_for: Vec<Option<usize>>,
+	detected: usize,
+	first_cycle: Option<Vec<WaitEdge>>,
+}
+
+// Aresta do ciclo: `thread` espera `lock`, que pertence a `owner`.
+#[derive(Clone, Copy, Debug)]
+pub struct WaitEdge {
+	pub thread: usize,
+	pub lock: usize,
+	pub owner: usize,
+}
+
+// Erro devolvido a vitima; o ciclo fica registrado no grafo (`first_cycle`).
+#[derive(Debug)]
+pub struct Deadlock;
+
+impl WaitForGraph {
+	pub fn new(locks: usize, threads: usize) -> Self {
+		Self {
+			state: Mutex::new(GraphState {
+				owner: vec![None; locks],
+				waiting_for: vec![None; threads],
+				detected: 0,
+				first_cycle: None,
+			}),
+			released: Condvar::new(),
+		}
+	}
+
+	fn acquire(&self, thread_id: usize, lock_id: usize) -> Result<(), Deadlock> {
+		let mut state = self.state.lock().expect("Mutex poisoned");
+		loop {
+			if state.owner[lock_id].is_none() {
+				state.owner[lock_id] = Some(thread_id);
+				state.waiting_for[thread_id] = None;
+				return Ok(());
+			}
+
+			if let Some(cycle) = find_cycle(&state, thread_id, lock_id) {
+				state.waiting_for[thread_id] = None;
+				state.detected += 1;
+				if state.first_cycle.is_none() {
+					state.first_cycle = Some(cycle);
+				}
+				return Err(Deadlock);
+			}
+
+			state.waiting_for[thread_id] = Some(lock_id);
+			state = self.released.wait(state).expect("Mutex poisoned");
+		}
+	}
+
+	fn release(&self, lock_id: usize) {
+		self.state.lock().expect("Mutex poisoned").owner[lock_id] = None;
+		self.released.notify_all();
+	}
+
+	pub fn detected(&self) -> usize {
+		self.state.lock().expect("Mutex poisoned").detected
+	}
+
+	pub fn first_cycle(&self) -> Option<Vec<WaitEdge>> {
+		self.state.lock().expect("Mutex poisoned").first_cycle.clone()
+	}
+}
+
+// Segue dono -> trava esperada pelo dono -> dono... a partir de `lock_id`. Como nenhuma thread
+// dorme fechando um ciclo, o caminho e uma cadeia simples e no maximo `threads` arestas.
+fn find_cycle(state: &GraphState, thread_id: usize, lock_id: usize) -> Option<Vec<WaitEdge>> {
+	let mut edges = Vec::new();
+	let mut waiter = thread_id;
+	let mut lock = lock_id;
+	while edges.len() <= state.waiting_for.len() {
+		let owner = state.owner[lock]?;
+		edges.push(WaitEdge {
+			thread: waiter,
+			lock,
+			owner,
+		});
+		if owner == thread_id {
+			return Some(edges);
+		}
+		waiter = owner;
+		lock = state.waiting_for[owner]?;
+	}
+	None
+}
+
+// Mutex cujo acesso passa pelo grafo: possuir a trava no grafo garante que o lock interno
+// nunca bloqueia, entao so o grafo decide quem espera.
+pub struct TrackedMutex<T> {
+	id: usize,
+	inner: Mutex<T>,
+	graph: Arc<WaitForGraph>,
+}
+
+pub struct TrackedGuard<'a, T> {
+	guard: Option<MutexGuard<'a, T>>,
+	owner: &'a TrackedMutex<T>,
+}
+
+impl<T> TrackedMutex<T> {
+	pub fn new(id: usize, value: T, graph: Arc<WaitForGraph>) -> Self {
+		Self {
+			id,
+			inner: Mutex::new(value),
+			graph,
+		}
+	}
+
+	pub fn lock(&self, thread_id: usize) -> Result<TrackedGuard<'_, T>, Deadlock> {
+		self.graph.acquire(thread_id, self.id)?;
+		Ok(TrackedGuard {
+			guard: Some(self.inner.lock().expect("Mutex poisoned")),
+			owner: self,
+		})
+	}
+}
+
+impl<T> Deref for TrackedGuard<'_, T> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		self.guard.as_ref().expect("Guard ja liberado")
+	}
+}
+
+impl<T> DerefMut for TrackedGuard<'_, T> {
+	fn deref_mut(&mut self) -> &mut T {
+		self.guard.as_mut().expect("Guard ja liberado")
+	}
+}
+
+impl<T> Drop for TrackedGuard<'_, T> {
+	fn drop(&mut self) {
+		// Solta o lock interno antes de liberar a posse no grafo.
+		drop(self.guard.take());
+		self.owner.graph.release(self.owner.id);
+	}
+}
+
+pub fn describe_cycle(cycle: &[WaitEdge]) -> String {
+	cycle
+		.iter()
+		.map(|edge| format!("T{} espera conta {} (dona T{})", edge.thread, edge.lock, edge.owner))
+		.collect::<Vec<_>>()
+		.join(" -> ")
+}
@@ -1,12 +1,18 @@
+mod placement;
+
+use placement::{report_placement, ThreadPlacement};
 use std::env;
-use std::io::{self, Write};
-use std::thread;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
 const RUNS: usize = 5;
+const JOIN_DEADLINE: Duration = Duration::from_millis(50);
 
 fn main() {
-	assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful");
+	const { assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful") };
 
 	let n = read_thread_count().unwrap_or_else(|err| {
 		eprintln!("{}", err);
@@ -19,7 +25,7 @@ fn main() {
 	println!("Total de execucoes temporizadas: {} ({} entram na media)", RUNS, RUNS - 1);
 
 	let (parallel_avg, parallel_times, parallel_outputs) =
-		measure_runs(|run| spawn_indexed_threads(n, run == 0));
+		measure_runs(|run| spawn_indexed_threads(n, run == 0, false));
 	let (sequential_avg, sequential_times, sequential_outputs) =
 		measure_runs(|run| sequential_indices(n, run == 0));
 
@@ -27,7 +33,7 @@ fn main() {
 		.iter()
 		.skip(1)
 		.zip(sequential_outputs.iter().skip(1))
-		.all(|(parallel, sequential)| parallel == sequential);
+		.all(|(parallel, sequential)| parallel.indices == *sequential);
 
 	println!("\nTempos paralelos (ms):");
 	log_durations(&parallel_times);
@@ -41,6 +47,134 @@ fn main() {
 	println!(
 		"Passagem de dados: cada thread recebe seu indice via closure `move`, que captura `i` ao criar a thread."
 	);
+
+	// Execucao extra fora da medicao: as amostras de sched_getcpu nao entram nos tempos acima.
+	let placement_run = spawn_indexed_threads(n, false, true);
+	println!("\nPosicionamento das threads (execucao extra fora da medicao, sched_getcpu no inicio/meio/fim):");
+	report_placement(&placement_run.placements);
+
+	if let Some(path) = env::args().nth(2) {
+		match export_placement_csv(&path, &placement_run.placements) {
+			Ok(()) => println!("  Mapa exportado para {}", path),
+			Err(err) => eprintln!("Falha ao exportar mapa para {}: {}", path, err),
+		}
+	}
+
+	report_timed_joins(n);
+}
+
+// Join com prazo: a thread envia o resultado por um canal de conclusao e main espera com recv_timeout.
+// Se o canal fechar sem mensagem, a thread entrou em panico.
+struct TimedJoin<T> {
+	handle: JoinHandle<()>,
+	result_rx: mpsc::Receiver<T>,
+}
+
+enum TimedJoinError<T> {
+	// Prazo expirado: a thread continua rodando e pode ser aguardada de novo.
+	TimedOut(TimedJoin<T>),
+	Panicked,
+}
+
+impl<T: Send + 'static> TimedJoin<T> {
+	fn spawn<F>(job: F) -> Self
+	where
+		F: FnOnce() -> T + Send + 'static,
+	{
+		let (result_tx, result_rx) = mpsc::channel();
+		let handle = thread::spawn(move || {
+			// Receptor descartado significa que ninguem aguarda mais o resultado.
+			let _ = result_tx.send(job());
+		});
+		Self { handle, result_rx }
+	}
+
+	fn join_timeout(self, timeout: Duration) -> Result<T, TimedJoinError<T>> {
+		match self.result_rx.recv_timeout(timeout) {
+			Ok(value) => {
+				self.handle.join().expect("Thread panicked after sending result");
+				Ok(value)
+			}
+			Err(RecvTimeoutError::Timeout) => Err(TimedJoinError::TimedOut(self)),
+			Err(RecvTimeoutError::Disconnected) => {
+				let _ = self.handle.join();
+				Err(TimedJoinError::Panicked)
+			}
+		}
+	}
+
+	fn join_deadline(self, deadline: Instant) -> Result<T, TimedJoinError<T>> {
+		self.join_timeout(deadline.saturating_duration_since(Instant::now()))
+	}
+
+	fn join(self) -> Result<T, TimedJoinError<T>> {
+		match self.result_rx.recv() {
+			Ok(value) => {
+				self.handle.join().expect("Thread panicked after sending result");
+				Ok(value)
+			}
+			Err(_) => {
+				let _ = self.handle.join();
+				Err(TimedJoinError::Panicked)
+			}
+		}
+	}
+}
+
+fn simulated_delay(index: usize) -> Duration {
+	// Distribuicao deterministica entre 0 e 80 ms: parte das threads ultrapassa o prazo.
+	Duration::from_millis(((index * 7) % 9) as u64 * 10)
+}
+
+fn report_timed_joins(n: usize) {
+	println!(
+		"\nJoin com prazo compartilhado de {} ms para {} threads:",
+		JOIN_DEADLINE.as_millis(),
+		n
+	);
+
+	let timed: Vec<_> = (0..n)
+		.map(|i| {
+			TimedJoin::spawn(move || {
+				thread::sleep(simulated_delay(i));
+				i
+			})
+		})
+		.collect();
+
+	let deadline = Instant::now() + JOIN_DEADLINE;
+	let mut on_time = Vec::new();
+	let mut timed_out = Vec::new();
+	let mut panicked = Vec::new();
+	let mut pending = Vec::new();
+
+	for (i, handle) in timed.into_iter().enumerate() {
+		match handle.join_deadline(deadline) {
+			Ok(index) => on_time.push(index),
+			Err(TimedJoinError::TimedOut(handle)) => {
+				timed_out.push(i);
+				pending.push(handle);
+			}
+			Err(TimedJoinError::Panicked) => panicked.push(i),
+		}
+	}
+
+	println!("  Dentro do prazo: {:?}", on_time);
+	for &i in &timed_out {
+		println!("  Thread {} excedeu o prazo (atraso simulado {} ms)", i, simulated_delay(i).as_millis());
+	}
+	if !panicked.is_empty() {
+		println!("  Threads em panico: {:?}", panicked);
+	}
+
+	// As atrasadas continuam rodando; aguarda sem prazo para encerrar o programa de forma limpa.
+	let late_finished = pending.into_iter().filter_map(|handle| handle.join().ok()).count();
+	println!(
+		"  Resumo: {} no prazo, {} excederam ({} concluiram depois)",
+		on_time.len(),
+		timed_out.len(),
+		late_finished
+	);
 }
 
 fn read_thread_count() -> Result<usize, String> {
@@ -64,9 +198,9 @@ fn read_thread_count() -> Result<usize, String> {
 		.map_err(|_| format!("Entrada invalida para N: {}", input.trim()))
 }
 
-fn measure_runs<F>(mut job: F) -> (f64, Vec<Duration>, Vec<Vec<usize>>)
+fn measure_runs<F, T>(mut job: F) -> (f64, Vec<Duration>, Vec<T>)
 where
-	F: FnMut(usize) -> Vec<usize>,
+	F: FnMut(usize) -> T,
 {
 	let mut durations = Vec::with_capacity(RUNS);
 	let mut outputs = Vec::with_capacity(RUNS);
@@ -98,23 +232,59 @@ fn log_durations(durations: &[Duration]) {
 	println!("  Obs.: primeira execucao funciona como aquecimento.");
 }
 
-fn spawn_indexed_threads(n: usize, should_print: bool) -> Vec<usize> {
+struct IndexedRun {
+	indices: Vec<usize>,
+	placements: Vec<ThreadPlacement>,
+}
+
+// Com `sample_placement` falso a thread so imprime e devolve o indice, como nas medicoes originais.
+fn spawn_indexed_threads(n: usize, should_print: bool, sample_placement: bool) -> IndexedRun {
 	let mut handles = Vec::with_capacity(n);
 
 	for i in 0..n {
 		handles.push(thread::spawn(move || {
+			let placement = sample_placement.then(|| ThreadPlacement::start(i));
 			if should_print {
 				println!("Thread {}", i);
 			}
-			i
+			let placement = placement.map(|mut placement| {
+				placement.mark_midway();
+				// Cede o processador para dar ao escalonador a chance de migrar a thread antes da ultima amostra.
+				thread::yield_now();
+				placement.finish()
+			});
+			(i, placement)
 		}));
 	}
 
 	// Coleta na mesma ordem de criacao para facilitar a validacao.
-	handles
+	let (indices, placements): (Vec<usize>, Vec<Option<ThreadPlacement>>) = handles
 		.into_iter()
 		.map(|handle| handle.join().expect("Thread panicked during execution"))
-		.collect()
+		.unzip();
+
+	IndexedRun {
+		indices,
+		placements: placements.into_iter().flatten().collect(),
+	}
+}
+
+fn export_placement_csv(path: &str, placements: &[ThreadPlacement]) -> io::Result<()> {
+	let mut writer = BufWriter::new(File::create(path)?);
+	writeln!(writer, "thread,cpu_inicio,cpu_meio,cpu_fim,migracoes")?;
+	for placement in placements {
+		let cpu = |sample: Option<usize>| sample.map_or_else(String::new, |cpu| cpu.to_string());
+		writeln!(
+			writer,
+			"{},{},{},{},{}",
+			placement.thread_id,
+			cpu(placement.start_cpu),
+			cpu(placement.mid_cpu),
+			cpu(placement.end_cpu),
+			placement.migrations()
+		)?;
+	}
+	writer.flush()
 }
 
 fn sequential_indices(n: usize, should_print: bool) -> Vec<usize> {
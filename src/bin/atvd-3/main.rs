@@ -1,3 +1,5 @@
+mod patterns;
+
 use std::env;
 use std::io::{self, Write};
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -7,9 +9,17 @@ use std::time::{Duration, Instant};
 
 const RUNS: usize = 5;
 const ITERATIONS_PER_THREAD: usize = 1_000_000;
+const YIELD_INTERVAL: usize = 1024;
+const DEFAULT_STUDY_SAMPLES: usize = 200;
+const DEFAULT_PATTERN_TRIALS: usize = 50;
+const STUDY_ITERATIONS: [usize; 3] = [1_000, 10_000, 100_000];
+// Intervalo 0 desliga o yield_now, deixando a preempcao como unica fonte de intercalacao.
+const STUDY_YIELD_INTERVALS: [usize; 3] = [0, 64, YIELD_INTERVAL];
+// Limites superiores (em % do esperado) das faixas do histograma de perdas; a ultima faixa e aberta.
+const LOSS_BUCKETS: [f64; 5] = [0.0, 1.0, 5.0, 25.0, 50.0];
 
 fn main() {
-	assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful");
+	const { assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful") };
 
 	let thread_count = read_thread_count().unwrap_or_else(|err| {
 		eprintln!("{}", err);
@@ -18,6 +28,23 @@ fn main() {
 
 	assert!(thread_count > 0, "Use um valor de threads maior que zero");
 
+	let mode = read_mode().unwrap_or_else(|err| {
+		eprintln!("{}", err);
+		std::process::exit(1);
+	});
+
+	match mode {
+		Mode::Measure => {}
+		Mode::Distribution { samples } => {
+			run_loss_study(thread_count, samples);
+			return;
+		}
+		Mode::Patterns { trials } => {
+			run_pattern_catalog(thread_count, trials);
+			return;
+		}
+	}
+
 	let expected_total = thread_count * ITERATIONS_PER_THREAD;
 
 	println!("Atividade 3 — Condicao de corrida na pratica");
@@ -29,7 +56,7 @@ fn main() {
 	println!("Total de execucoes temporizadas: {} ({} entram na media)", RUNS, RUNS - 1);
 
 	let (parallel_avg, parallel_times, parallel_outputs) =
-		measure_runs(|run| race_condition_counter(thread_count, run == 0));
+		measure_runs(|run| race_condition_counter(thread_count, ITERATIONS_PER_THREAD, YIELD_INTERVAL, run == 0));
 	let (sequential_avg, sequential_times, sequential_outputs) =
 		measure_runs(|run| sequential_counter(thread_count, run == 0));
 
@@ -90,6 +117,42 @@ fn read_thread_count() -> Result<usize, String> {
 		.map_err(|_| format!("Entrada invalida para threads: {}", input.trim()))
 }
 
+enum Mode {
+	Measure,
+	Distribution { samples: usize },
+	Patterns { trials: usize },
+}
+
+fn read_mode() -> Result<Mode, String> {
+	let Some(mode) = env::args().nth(2) else {
+		return Ok(Mode::Measure);
+	};
+
+	match mode.as_str() {
+		"medir" => Ok(Mode::Measure),
+		"distribuicao" => Ok(Mode::Distribution {
+			samples: read_repetitions(DEFAULT_STUDY_SAMPLES)?,
+		}),
+		"padroes" => Ok(Mode::Patterns {
+			trials: read_repetitions(DEFAULT_PATTERN_TRIALS)?,
+		}),
+		other => Err(format!("Modo desconhecido: {} (use medir, distribuicao ou padroes)", other)),
+	}
+}
+
+fn read_repetitions(default: usize) -> Result<usize, String> {
+	let repetitions = match env::args().nth(3) {
+		Some(arg) => arg
+			.parse::<usize>()
+			.map_err(|_| format!("Argumento invalido para numero de amostras: {}", arg))?,
+		None => default,
+	};
+	if repetitions == 0 {
+		return Err(String::from("Use um numero de amostras maior que zero"));
+	}
+	Ok(repetitions)
+}
+
 fn measure_runs<F>(mut job: F) -> (f64, Vec<Duration>, Vec<usize>)
 where
 	F: FnMut(usize) -> usize,
@@ -124,18 +187,18 @@ fn log_durations(durations: &[Duration]) {
 	println!("  Obs.: primeira execucao funciona como aquecimento.");
 }
 
-fn race_condition_counter(thread_count: usize, should_print: bool) -> usize {
+fn race_condition_counter(thread_count: usize, iterations: usize, yield_interval: usize, should_print: bool) -> usize {
 	let counter = Arc::new(AtomicUsize::new(0));
 	let mut handles = Vec::with_capacity(thread_count);
 
 	for thread_id in 0..thread_count {
 		let counter_clone = Arc::clone(&counter);
 		handles.push(thread::spawn(move || {
-			for iter in 0..ITERATIONS_PER_THREAD {
+			for iter in 0..iterations {
 				let current = counter_clone.load(Ordering::Relaxed);
 				// Atualizacao nao atomica (load + store) que causa perda quando outras threads escrevem entre as operacoes.
 				counter_clone.store(current + 1, Ordering::Relaxed);
-				if iter % 1024 == 0 {
+				if yield_interval > 0 && iter % yield_interval == 0 {
 					thread::yield_now();
 				}
 			}
@@ -165,4 +228,204 @@ fn sequential_counter(thread_count: usize, should_print: bool) -> usize {
 	}
 
 	counter
-}
\ No newline at end of file
+}
+
+struct LossStats {
+	threads: usize,
+	iterations: usize,
+	yield_interval: usize,
+	expected: usize,
+	min: usize,
+	median: f64,
+	max: usize,
+	mean: f64,
+	correct_runs: usize,
+	samples: usize,
+	histogram: [usize; LOSS_BUCKETS.len() + 1],
+}
+
+impl LossStats {
+	fn from_losses(threads: usize, iterations: usize, yield_interval: usize, mut losses: Vec<usize>) -> Self {
+		losses.sort_unstable();
+		let expected = threads * iterations;
+		let samples = losses.len();
+
+		let mut histogram = [0usize; LOSS_BUCKETS.len() + 1];
+		for &loss in &losses {
+			let percent = loss_percent(loss, expected);
+			let bucket = LOSS_BUCKETS
+				.iter()
+				.position(|&limit| percent <= limit)
+				.unwrap_or(LOSS_BUCKETS.len());
+			histogram[bucket] += 1;
+		}
+
+		Self {
+			threads,
+			iterations,
+			yield_interval,
+			expected,
+			min: losses[0],
+			median: median(&losses),
+			max: losses[samples - 1],
+			mean: losses.iter().sum::<usize>() as f64 / samples as f64,
+			correct_runs: losses.iter().filter(|&&loss| loss == 0).count(),
+			samples,
+			histogram,
+		}
+	}
+
+	fn correct_probability(&self) -> f64 {
+		self.correct_runs as f64 / self.samples as f64
+	}
+
+	fn mean_loss_percent(&self) -> f64 {
+		if self.expected == 0 {
+			return 0.0;
+		}
+		self.mean / self.expected as f64 * 100.0
+	}
+}
+
+// Com numero par de amostras a mediana e a media dos dois valores centrais.
+fn median(sorted: &[usize]) -> f64 {
+	let middle = sorted.len() / 2;
+	if sorted.len().is_multiple_of(2) {
+		(sorted[middle - 1] + sorted[middle]) as f64 / 2.0
+	} else {
+		sorted[middle] as f64
+	}
+}
+
+fn loss_percent(loss: usize, expected: usize) -> f64 {
+	if expected == 0 {
+		return 0.0;
+	}
+	loss as f64 / expected as f64 * 100.0
+}
+
+fn study_thread_counts(max_threads: usize) -> Vec<usize> {
+	// Potencias de dois ate o maximo informado, incluindo o proprio maximo.
+	let mut counts: Vec<usize> = std::iter::successors(Some(1usize), |&count| count.checked_mul(2))
+		.take_while(|&count| count < max_threads)
+		.collect();
+	counts.push(max_threads);
+	counts
+}
+
+fn yield_label(yield_interval: usize) -> String {
+	if yield_interval == 0 {
+		String::from("sem yield")
+	} else {
+		format!("a cada {}", yield_interval)
+	}
+}
+
+fn run_loss_study(max_threads: usize, samples: usize) {
+	let thread_counts = study_thread_counts(max_threads);
+
+	println!("Atividade 3 — Distribuicao de incrementos perdidos");
+	println!(
+		"{} amostras por configuracao | threads {:?} | iteracoes {:?} | yield {:?}",
+		samples,
+		thread_counts,
+		STUDY_ITERATIONS,
+		STUDY_YIELD_INTERVALS
+	);
+
+	let mut table = Vec::new();
+	for &threads in &thread_counts {
+		for &iterations in &STUDY_ITERATIONS {
+			for &yield_interval in &STUDY_YIELD_INTERVALS {
+				let expected = threads * iterations;
+				let losses: Vec<usize> = (0..samples)
+					.map(|_| expected.saturating_sub(race_condition_counter(threads, iterations, yield_interval, false)))
+					.collect();
+				table.push(LossStats::from_losses(threads, iterations, yield_interval, losses));
+			}
+		}
+	}
+
+	println!("\nPerdas por configuracao (incrementos perdidos):");
+	println!(
+		"Threads | Iteracoes | Yield       |    Minimo |   Mediana |    Maximo | Perda media | P(correto)"
+	);
+	for stats in &table {
+		println!(
+			"{:>7} | {:>9} | {:<11} | {:>9} | {:>9.1} | {:>9} | {:>10.3}% | {:>10.3}",
+			stats.threads,
+			stats.iterations,
+			yield_label(stats.yield_interval),
+			stats.min,
+			stats.median,
+			stats.max,
+			stats.mean_loss_percent(),
+			stats.correct_probability()
+		);
+	}
+
+	println!("\nHistograma (execucoes por faixa de perda, em % do esperado):");
+	let mut header = String::from("Threads | Iteracoes | Yield      ");
+	let mut lower = None;
+	for &limit in &LOSS_BUCKETS {
+		header.push_str(&match lower {
+			None => format!(" | {:>9}", format!("={}%", limit)),
+			Some(lower) => format!(" | {:>9}", format!("{}-{}%", lower, limit)),
+		});
+		lower = Some(limit);
+	}
+	header.push_str(&format!(" | {:>9}", format!(">{}%", LOSS_BUCKETS[LOSS_BUCKETS.len() - 1])));
+	println!("{}", header);
+	for stats in &table {
+		let buckets: String = stats.histogram.iter().map(|count| format!(" | {:>9}", count)).collect();
+		println!(
+			"{:>7} | {:>9} | {:<11}{}",
+			stats.threads,
+			stats.iterations,
+			yield_label(stats.yield_interval),
+			buckets
+		);
+	}
+
+	println!("\nEscalonamento com a contencao (media sobre iteracoes e yields):");
+	println!("Threads | Perda media | P(correto)");
+	for &threads in &thread_counts {
+		let rows: Vec<&LossStats> = table.iter().filter(|stats| stats.threads == threads).collect();
+		let mean_percent = rows.iter().map(|stats| stats.mean_loss_percent()).sum::<f64>() / rows.len() as f64;
+		let probability = rows.iter().map(|stats| stats.correct_probability()).sum::<f64>() / rows.len() as f64;
+		println!("{:>7} | {:>10.3}% | {:>10.3}", threads, mean_percent, probability);
+	}
+
+	println!(
+		"\nLeitura: com uma unica thread nao ha intercalacao e a perda e sempre zero; mais threads e mais iteracoes \
+	aumentam as janelas entre load e store em que outra thread escreve, e yields frequentes forcam trocas de contexto no meio dessas janelas."
+	);
+}
+
+fn run_pattern_catalog(thread_count: usize, trials: usize) {
+	println!("Atividade 3 — Catalogo de condicoes de corrida");
+	println!(
+		"{} tentativas por padrao com {} threads (minimo de 2)",
+		trials,
+		thread_count.max(2)
+	);
+
+	let reports = patterns::run_catalog(thread_count, trials);
+
+	println!("\nPadrao                     | Observacoes | Anomalias |   Taxa | Detalhe");
+	for report in &reports {
+		println!(
+			"{:<26} | {:>11} | {:>9} | {:>6.4} | {}",
+			report.name,
+			report.observations,
+			report.anomalies,
+			report.rate(),
+			report.detail
+		);
+	}
+
+	println!(
+		"\nLeitura: todos os padroes compartilham a causa do contador — uma decisao tomada sobre um valor lido \
+	que pode ter mudado antes da escrita. Observacoes contam tentativas (inicializacao, transferencia), leituras (valor rasgado) ou pops (ABA)."
+	);
+}
@@ -0,0 +1,304 @@
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU32, AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+// Cada padrao usa atomicos com load e store separados: o codigo e Rust seguro,
+// mas a sequencia ler-decidir-escrever nao e indivisivel e abre a mesma janela do contador.
+const TORN_ITERATIONS: usize = 100_000;
+const TRANSFERS_PER_THREAD: usize = 10_000;
+const ACCOUNT_COUNT: usize = 8;
+const INITIAL_BALANCE: i64 = 1_000;
+const STACK_NODES: usize = 4;
+const STACK_OPS_PER_THREAD: usize = 10_000;
+const NULL: usize = usize::MAX;
+
+pub struct PatternReport {
+	pub name: &'static str,
+	pub observations: usize,
+	pub anomalies: usize,
+	pub detail: String,
+}
+
+impl PatternReport {
+	pub fn rate(&self) -> f64 {
+		if self.observations == 0 {
+			return 0.0;
+		}
+		self.anomalies as f64 / self.observations as f64
+	}
+}
+
+pub fn run_catalog(thread_count: usize, trials: usize) -> Vec<PatternReport> {
+	// Todos os padroes precisam de pelo menos duas threads para haver intercalacao.
+	let threads = thread_count.max(2);
+	vec![
+		lazy_init_race(threads, trials),
+		torn_read_race(threads, trials),
+		bank_transfer_race(threads, trials),
+		aba_stack_race(threads, trials),
+	]
+}
+
+fn spawn_all<F>(threads: usize, worker: F)
+where
+	F: Fn(usize) + Send + Sync + 'static,
+{
+	let worker = Arc::new(worker);
+	// A barreira solta todas as threads juntas para maximizar a sobreposicao.
+	let barrier = Arc::new(Barrier::new(threads));
+	let handles: Vec<_> = (0..threads)
+		.map(|thread_id| {
+			let worker = Arc::clone(&worker);
+			let barrier = Arc::clone(&barrier);
+			thread::spawn(move || {
+				barrier.wait();
+				worker(thread_id);
+			})
+		})
+		.collect();
+
+	for handle in handles {
+		handle.join().expect("Thread panicked during execution");
+	}
+}
+
+// Check-then-act: todas verificam a flag antes que alguma a marque, e a inicializacao roda mais de uma vez.
+fn lazy_init_race(threads: usize, trials: usize) -> PatternReport {
+	let mut anomalies = 0usize;
+	let mut extra_inits = 0usize;
+
+	for _ in 0..trials {
+		let initialized = Arc::new(AtomicBool::new(false));
+		let init_runs = Arc::new(AtomicUsize::new(0));
+		let value = Arc::new(AtomicUsize::new(0));
+
+		let (initialized_clone, init_runs_clone, value_clone) =
+			(Arc::clone(&initialized), Arc::clone(&init_runs), Arc::clone(&value));
+		spawn_all(threads, move |thread_id| {
+			if !initialized_clone.load(Ordering::Acquire) {
+				// Janela entre o teste e a acao: outra thread pode passar pelo mesmo teste aqui.
+				thread::yield_now();
+				init_runs_clone.fetch_add(1, Ordering::Relaxed);
+				value_clone.store(thread_id + 1, Ordering::Relaxed);
+				initialized_clone.store(true, Ordering::Release);
+			}
+		});
+
+		let runs = init_runs.load(Ordering::Relaxed);
+		if runs > 1 {
+			anomalies += 1;
+			extra_inits += runs - 1;
+		}
+	}
+
+	PatternReport {
+		name: "Inicializacao preguicosa",
+		observations: trials,
+		anomalies,
+		detail: format!("{} inicializacoes extras no total", extra_inits),
+	}
+}
+
+// Valor de duas palavras gravado em metades: o leitor pode ver a metade alta nova com a baixa antiga.
+fn torn_read_race(threads: usize, trials: usize) -> PatternReport {
+	let mut reads = 0usize;
+	let mut torn = 0usize;
+
+	for _ in 0..trials {
+		let high = Arc::new(AtomicU32::new(0));
+		let low = Arc::new(AtomicU32::new(0));
+		let read_count = Arc::new(AtomicUsize::new(0));
+		let torn_count = Arc::new(AtomicUsize::new(0));
+
+		let (high_clone, low_clone) = (Arc::clone(&high), Arc::clone(&low));
+		let (read_clone, torn_clone) = (Arc::clone(&read_count), Arc::clone(&torn_count));
+		spawn_all(threads, move |thread_id| {
+			if thread_id == 0 {
+				// Escritor: as duas metades sempre carregam o mesmo numero de versao.
+				for version in 1..=TORN_ITERATIONS as u32 {
+					high_clone.store(version, Ordering::Relaxed);
+					if version % 1024 == 0 {
+						thread::yield_now();
+					}
+					low_clone.store(version, Ordering::Relaxed);
+				}
+			} else {
+				for iter in 0..TORN_ITERATIONS {
+					let observed_high = high_clone.load(Ordering::Relaxed);
+					let observed_low = low_clone.load(Ordering::Relaxed);
+					read_clone.fetch_add(1, Ordering::Relaxed);
+					if observed_high != observed_low {
+						torn_clone.fetch_add(1, Ordering::Relaxed);
+					}
+					if iter % 1024 == 0 {
+						thread::yield_now();
+					}
+				}
+			}
+		});
+
+		reads += read_count.load(Ordering::Relaxed);
+		torn += torn_count.load(Ordering::Relaxed);
+	}
+
+	PatternReport {
+		name: "Valor rasgado (2 metades)",
+		observations: reads,
+		anomalies: torn,
+		detail: format!("{} leituras inconsistentes", torn),
+	}
+}
+
+// Transferencia ler-modificar-escrever: debito e credito separados perdem ou criam dinheiro.
+fn bank_transfer_race(threads: usize, trials: usize) -> PatternReport {
+	let expected_total = INITIAL_BALANCE * ACCOUNT_COUNT as i64;
+	let mut anomalies = 0usize;
+	let mut total_drift = 0i64;
+
+	for _ in 0..trials {
+		let accounts: Arc<Vec<AtomicI64>> =
+			Arc::new((0..ACCOUNT_COUNT).map(|_| AtomicI64::new(INITIAL_BALANCE)).collect());
+
+		let accounts_clone = Arc::clone(&accounts);
+		spawn_all(threads, move |thread_id| {
+			for iter in 0..TRANSFERS_PER_THREAD {
+				let from = (thread_id * 3 + iter) % ACCOUNT_COUNT;
+				let to = (from + 1 + iter % (ACCOUNT_COUNT - 1)) % ACCOUNT_COUNT;
+				let amount = (iter % 7 + 1) as i64;
+
+				let from_balance = accounts_clone[from].load(Ordering::Relaxed);
+				if iter % 256 == 0 {
+					thread::yield_now();
+				}
+				accounts_clone[from].store(from_balance - amount, Ordering::Relaxed);
+				let to_balance = accounts_clone[to].load(Ordering::Relaxed);
+				accounts_clone[to].store(to_balance + amount, Ordering::Relaxed);
+			}
+		});
+
+		let final_total: i64 = accounts.iter().map(|account| account.load(Ordering::Relaxed)).sum();
+		if final_total != expected_total {
+			anomalies += 1;
+			total_drift += final_total - expected_total;
+		}
+	}
+
+	PatternReport {
+		name: "Transferencia bancaria",
+		observations: trials,
+		anomalies,
+		detail: format!("saldo total desviou {} no acumulado", total_drift),
+	}
+}
+
+// Pilha de Treiber sobre indices: pop le head e next, e o CAS aceita head = A mesmo que A
+// tenha saido e voltado (A -> B -> A) com outro next, religando um no que esta em uso.
+fn aba_stack_race(threads: usize, trials: usize) -> PatternReport {
+	let mut pops = 0usize;
+	let mut double_pops = 0usize;
+
+	for _ in 0..trials {
+		let stack = Arc::new(IndexStack::new(STACK_NODES));
+		let pop_count = Arc::new(AtomicUsize::new(0));
+		let aba_count = Arc::new(AtomicUsize::new(0));
+
+		let (stack_clone, pop_clone, aba_clone) =
+			(Arc::clone(&stack), Arc::clone(&pop_count), Arc::clone(&aba_count));
+		spawn_all(threads, move |_| {
+			// Detector: um no so pode ter um dono; swap devolvendo true indica pop duplicado.
+			let claim = |node: usize| {
+				pop_clone.fetch_add(1, Ordering::Relaxed);
+				if stack_clone.owned[node].swap(true, Ordering::AcqRel) {
+					aba_clone.fetch_add(1, Ordering::Relaxed);
+					return false;
+				}
+				true
+			};
+			let release = |node: usize| {
+				stack_clone.owned[node].store(false, Ordering::Release);
+				stack_clone.push(node);
+			};
+
+			for iter in 0..STACK_OPS_PER_THREAD {
+				let widen_window = iter % 16 == 0;
+				// Retira dois nos e devolve o primeiro antes do segundo: o topo volta a ser A com outro next.
+				let first = stack_clone.pop(widen_window).filter(|&node| claim(node));
+				let second = stack_clone.pop(false).filter(|&node| claim(node));
+				if let Some(node) = first {
+					release(node);
+				}
+				if widen_window {
+					thread::yield_now();
+				}
+				if let Some(node) = second {
+					release(node);
+				}
+			}
+		});
+
+		pops += pop_count.load(Ordering::Relaxed);
+		double_pops += aba_count.load(Ordering::Relaxed);
+	}
+
+	PatternReport {
+		name: "ABA em pilha com CAS",
+		observations: pops,
+		anomalies: double_pops,
+		detail: format!("{} nos retirados por duas threads ao mesmo tempo", double_pops),
+	}
+}
+
+struct IndexStack {
+	head: AtomicUsize,
+	next: Vec<AtomicUsize>,
+	owned: Vec<AtomicBool>,
+}
+
+impl IndexStack {
+	fn new(nodes: usize) -> Self {
+		let next = (0..nodes)
+			.map(|node| AtomicUsize::new(if node + 1 < nodes { node + 1 } else { NULL }))
+			.collect();
+		let owned = (0..nodes).map(|_| AtomicBool::new(false)).collect();
+		Self {
+			head: AtomicUsize::new(if nodes > 0 { 0 } else { NULL }),
+			next,
+			owned,
+		}
+	}
+
+	fn pop(&self, widen_window: bool) -> Option<usize> {
+		loop {
+			let head = self.head.load(Ordering::Acquire);
+			if head == NULL {
+				return None;
+			}
+			let next = self.next[head].load(Ordering::Acquire);
+			if widen_window {
+				// Entre ler next e o CAS, outras threads podem retirar e devolver `head`.
+				thread::yield_now();
+			}
+			if self
+				.head
+				.compare_exchange(head, next, Ordering::AcqRel, Ordering::Acquire)
+				.is_ok()
+			{
+				return Some(head);
+			}
+		}
+	}
+
+	fn push(&self, node: usize) {
+		loop {
+			let head = self.head.load(Ordering::Acquire);
+			self.next[node].store(head, Ordering::Release);
+			if self
+				.head
+				.compare_exchange(head, node, Ordering::AcqRel, Ordering::Acquire)
+				.is_ok()
+			{
+				return;
+			}
+		}
+	}
+}
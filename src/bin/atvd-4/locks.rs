@@ -0,0 +1,331 @@
+use crate::lock_stats::InstrumentedMutex;
+use std::hint;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+// Depois de SPIN_LIMIT voltas ocupadas a espera cede o processador: sem isso, com mais threads
+// que nucleos, quem espera gasta a fatia de tempo inteira enquanto o dono da trava nem roda.
+const SPIN_LIMIT: u32 = 64;
+const MAX_BACKOFF: u32 = 1 << 10;
+const NONE: usize = usize::MAX;
+
+// Trava intercambiavel usada por `locked_counter`. `thread_id` (0..threads) identifica quem
+// adquire; apenas o MCS precisa dele, para localizar o no da thread na fila.
+pub trait RawLock: Send + Sync + 'static {
+	const NAME: &'static str;
+
+	fn with_threads(threads: usize) -> Self;
+
+	fn with_lock<R>(&self, thread_id: usize, critical: impl FnOnce() -> R) -> R;
+}
+
+fn relax(spins: &mut u32) {
+	if *spins < SPIN_LIMIT {
+		*spins += 1;
+		hint::spin_loop();
+	} else {
+		thread::yield_now();
+	}
+}
+
+pub struct StdMutex(Mutex<()>);
+
+impl RawLock for StdMutex {
+	const NAME: &'static str = "std::sync::Mutex";
+
+	fn with_threads(_threads: usize) -> Self {
+		Self(Mutex::new(()))
+	}
+
+	fn with_lock<R>(&self, _thread_id: usize, critical: impl FnOnce() -> R) -> R {
+		let _guard = self.0.lock().expect("Mutex poisoned");
+		critical()
+	}
+}
+
+// Mutex da std com medicao de espera, posse e disputa (modo contencao).
+impl RawLock for InstrumentedMutex<()> {
+	const NAME: &'static str = "Mutex instrumentado";
+
+	fn with_threads(threads: usize) -> Self {
+		InstrumentedMutex::new((), threads)
+	}
+
+	fn with_lock<R>(&self, thread_id: usize, critical: impl FnOnce() -> R) -> R {
+		let _guard = self.lock(thread_id);
+		critical()
+	}
+}
+
+// Test-and-set: toda tentativa e uma escrita (swap), o que faz a linha de cache pular entre nucleos.
+pub struct TasSpinLock {
+	locked: AtomicBool,
+}
+
+impl RawLock for TasSpinLock {
+	const NAME: &'static str = "TAS spinlock";
+
+	fn with_threads(_threads: usize) -> Self {
+		Self {
+			locked: AtomicBool::new(false),
+		}
+	}
+
+	fn with_lock<R>(&self, _thread_id: usize, critical: impl FnOnce() -> R) -> R {
+		let mut spins = 0;
+		while self.locked.swap(true, Ordering::Acquire) {
+			relax(&mut spins);
+		}
+		let result = critical();
+		self.locked.store(false, Ordering::Release);
+		result
+	}
+}
+
+// Test-and-test-and-set: espera lendo (linha compartilhada) e so tenta o swap quando parece livre;
+// ao perder a disputa, recua por um tempo que dobra a cada falha.
+pub struct TtasBackoffLock {
+	locked: AtomicBool,
+}
+
+impl RawLock for TtasBackoffLock {
+	const NAME: &'static str = "TTAS + backoff";
+
+	fn with_threads(_threads: usize) -> Self {
+		Self {
+			locked: AtomicBool::new(false),
+		}
+	}
+
+	fn with_lock<R>(&self, _thread_id: usize, critical: impl FnOnce() -> R) -> R {
+		let mut backoff = 1;
+		loop {
+			let mut spins = 0;
+			while self.locked.load(Ordering::Relaxed) {
+				relax(&mut spins);
+			}
+			if !self.locked.swap(true, Ordering::Acquire) {
+				break;
+			}
+			for _ in 0..backoff {
+				hint::spin_loop();
+			}
+			if backoff < MAX_BACKOFF {
+				backoff *= 2;
+			} else {
+				thread::yield_now();
+			}
+		}
+		let result = critical();
+		self.locked.store(false, Ordering::Release);
+		result
+	}
+}
+
+// Ticket lock: fila FIFO implicita; cada thread pega uma senha e espera ser chamada.
+pub struct TicketLock {
+	next_ticket: AtomicUsize,
+	now_serving: AtomicUsize,
+}
+
+impl RawLock for TicketLock {
+	const NAME: &'static str = "Ticket lock";
+
+	fn with_threads(_threads: usize) -> Self {
+		Self {
+			next_ticket: AtomicUsize::new(0),
+			now_serving: AtomicUsize::new(0),
+		}
+	}
+
+	fn with_lock<R>(&self, _thread_id: usize, critical: impl FnOnce() -> R) -> R {
+		let ticket = self.next_ticket.fetch_add(1, Ordering::Relaxed);
+		let mut spins = 0;
+		while self.now_serving.load(Ordering::Acquire) != ticket {
+			relax(&mut spins);
+		}
+		let result = critical();
+		// Somente o dono escreve now_serving, entao load + store basta.
+		self.now_serving.store(ticket + 1, Ordering::Release);
+		result
+	}
+}
+
+// MCS: fila explicita em que cada thread gira sobre a propria flag. Os nos ficam num vetor
+// indexado por thread_id e `next`/`tail` guardam indices, o que dispensa ponteiros crus.
+pub struct McsLock {
+	tail: AtomicUsize,
+	nodes: Vec<McsNode>,
+}
+
+struct McsNode {
+	next: AtomicUsize,
+	waiting: AtomicBool,
+}
+
+impl McsLock {
+	fn acquire(&self, thread_id: usize) {
+		let node = &self.nodes[thread_id];
+		node.next.store(NONE, Ordering::Relaxed);
+		node.waiting.store(true, Ordering::Relaxed);
+
+		let previous = self.tail.swap(thread_id, Ordering::AcqRel);
+		if previous == NONE {
+			return;
+		}
+
+		self.nodes[previous].next.store(thread_id, Ordering::Release);
+		let mut spins = 0;
+		while node.waiting.load(Ordering::Acquire) {
+			relax(&mut spins);
+		}
+	}
+
+	fn release(&self, thread_id: usize) {
+		let node = &self.nodes[thread_id];
+		let mut next = node.next.load(Ordering::Acquire);
+		if next == NONE {
+			// Sem sucessor visivel: se ainda somos a cauda, a fila esvazia.
+			if self
+				.tail
+				.compare_exchange(thread_id, NONE, Ordering::Release, Ordering::Relaxed)
+				.is_ok()
+			{
+				return;
+			}
+			// Um sucessor ja trocou a cauda mas ainda nao se ligou a nos.
+			let mut spins = 0;
+			loop {
+				next = node.next.load(Ordering::Acquire);
+				if next != NONE {
+					break;
+				}
+				relax(&mut spins);
+			}
+		}
+		self.nodes[next].waiting.store(false, Ordering::Release);
+	}
+}
+
+impl RawLock for McsLock {
+	const NAME: &'static str = "MCS queue lock";
+
+	fn with_threads(threads: usize) -> Self {
+		Self {
+			tail: AtomicUsize::new(NONE),
+			nodes: (0..threads)
+				.map(|_| McsNode {
+					next: AtomicUsize::new(NONE),
+					waiting: AtomicBool::new(false),
+				})
+				.collect(),
+		}
+	}
+
+	fn with_lock<R>(&self, thread_id: usize, critical: impl FnOnce() -> R) -> R {
+		self.acquire(thread_id);
+		let result = critical();
+		self.release(thread_id);
+		result
+	}
+}
+
+// Mutex sobre futex (Drepper, "Futexes Are Tricky"): 0 = livre, 1 = travado, 2 = travado com
+// espera. Quem nao consegue a trava dorme no kernel em vez de girar.
+pub struct FutexMutex {
+	state: AtomicU32,
+}
+
+impl FutexMutex {
+	fn acquire(&self) {
+		let mut current = match self.state.compare_exchange(0, 1, Ordering::Acquire, Ordering::Relaxed) {
+			Ok(_) => return,
+			Err(current) => current,
+		};
+		if current != 2 {
+			current = self.state.swap(2, Ordering::Acquire);
+		}
+		while current != 0 {
+			futex::wait(&self.state, 2);
+			current = self.state.swap(2, Ordering::Acquire);
+		}
+	}
+
+	fn release(&self) {
+		if self.state.fetch_sub(1, Ordering::Release) != 1 {
+			self.state.store(0, Ordering::Release);
+			futex::wake_one(&self.state);
+		}
+	}
+}
+
+impl RawLock for FutexMutex {
+	const NAME: &'static str = "Futex mutex";
+
+	fn with_threads(_threads: usize) -> Self {
+		Self {
+			state: AtomicU32::new(0),
+		}
+	}
+
+	fn with_lock<R>(&self, _thread_id: usize, critical: impl FnOnce() -> R) -> R {
+		self.acquire();
+		let result = critical();
+		self.release();
+		result
+	}
+}
+
+#[cfg(all(target_os = "linux", any(target_arch = "x86_64", target_arch = "aarch64")))]
+mod futex {
+	use std::ptr;
+	use std::sync::atomic::AtomicU32;
+
+	#[cfg(target_arch = "x86_64")]
+	const SYS_FUTEX: i64 = 202;
+	#[cfg(target_arch = "aarch64")]
+	const SYS_FUTEX: i64 = 98;
+	const FUTEX_WAIT_PRIVATE: i32 = 128;
+	const FUTEX_WAKE_PRIVATE: i32 = 129;
+
+	unsafe extern "C" {
+		fn syscall(number: i64, ...) -> i64;
+	}
+
+	pub fn wait(state: &AtomicU32, expected: u32) {
+		// SAFETY: o endereco vem de um AtomicU32 vivo durante a chamada; o kernel so o le e
+		// retorna imediatamente se o valor ja nao for `expected`. Sem timeout (ponteiro nulo).
+		unsafe {
+			syscall(
+				SYS_FUTEX,
+				state.as_ptr(),
+				FUTEX_WAIT_PRIVATE,
+				expected,
+				ptr::null::<u8>(),
+			);
+		}
+	}
+
+	pub fn wake_one(state: &AtomicU32) {
+		// SAFETY: mesmo endereco valido; FUTEX_WAKE apenas acorda ate 1 thread esperando nele.
+		unsafe {
+			syscall(SYS_FUTEX, state.as_ptr(), FUTEX_WAKE_PRIVATE, 1u32);
+		}
+	}
+}
+
+// Sem futex disponivel a espera degrada para ceder o processador e tentar de novo.
+#[cfg(not(all(target_os = "linux", any(target_arch = "x86_64", target_arch = "aarch64"))))]
+mod futex {
+	use std::sync::atomic::{AtomicU32, Ordering};
+	use std::thread;
+
+	pub fn wait(state: &AtomicU32, expected: u32) {
+		if state.load(Ordering::Relaxed) == expected {
+			thread::yield_now();
+		}
+	}
+
+	pub fn wake_one(_state: &AtomicU32) {}
+}
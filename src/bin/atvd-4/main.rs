@@ -1,15 +1,24 @@
+mod lock_stats;
+mod locks;
+mod poisoning;
+mod workload;
+
+use lock_stats::{print_lock_reports, InstrumentedMutex};
+use locks::{FutexMutex, McsLock, RawLock, StdMutex, TasSpinLock, TicketLock, TtasBackoffLock};
+use poisoning::run_policies;
 use std::env;
 use std::io::{self, Write};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
+use workload::{SpinCalibration, Workload};
 
 const RUNS: usize = 5;
 const ITERATIONS_PER_THREAD: usize = 1_000_000;
 
 fn main() {
-	assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful");
+	const { assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful") };
 
 	let thread_count = read_thread_count().unwrap_or_else(|err| {
 		eprintln!("{}", err);
@@ -18,17 +27,47 @@ fn main() {
 
 	assert!(thread_count > 0, "Use um valor de threads maior que zero");
 
-	let expected_total = thread_count * ITERATIONS_PER_THREAD;
+	let mode = read_mode().unwrap_or_else(|err| {
+		eprintln!("{}", err);
+		std::process::exit(1);
+	});
+
+	match mode {
+		Mode::Measure => {}
+		Mode::Contention => {
+			run_contention_report(thread_count);
+			return;
+		}
+		Mode::Poisoning => {
+			run_poisoning_report(thread_count);
+			return;
+		}
+	}
+
+	let calibration = SpinCalibration::measure();
+	let workload = read_workload(&calibration).unwrap_or_else(|err| {
+		eprintln!("{}", err);
+		std::process::exit(1);
+	});
+
+	let expected_total = thread_count * workload.iterations;
 
 	println!("Atividade 4 — Corrigindo com exclusao mutua");
-	println!("Cada thread incrementa o contador {} vezes; valor esperado = {}", ITERATIONS_PER_THREAD, expected_total);
+	println!("Cada thread incrementa o contador {} vezes; valor esperado = {}", workload.iterations, expected_total);
+	println!(
+		"Trabalho por iteracao: {} ns na secao critica, {} ns fora dela (calibracao: {:.3} voltas/ns)",
+		workload.critical_ns,
+		workload.outside_ns,
+		calibration.spins_per_ns()
+	);
 	println!("Total de execucoes temporizadas: {} ({} entram na media)", RUNS, RUNS - 1);
 
-	let (race_avg, race_times, race_outputs) = measure_runs(|run| race_condition_counter(thread_count, run == 0));
+	let (race_avg, race_times, race_outputs) =
+		measure_runs(|run| race_condition_counter(thread_count, workload, run == 0));
 	let (locked_avg, locked_times, locked_outputs) =
-		measure_runs(|run| locked_counter(thread_count, run == 0));
+		measure_runs(|run| locked_counter(thread_count, workload, run == 0));
 	let (sequential_avg, sequential_times, sequential_outputs) =
-		measure_runs(|run| sequential_counter(thread_count, run == 0));
+		measure_runs(|run| sequential_counter(thread_count, workload, run == 0));
 
 	let race_final = *race_outputs.last().unwrap_or(&0);
 	let locked_final = *locked_outputs.last().unwrap_or(&0);
@@ -54,6 +93,77 @@ fn main() {
 		"Analise: a exclusao mutua elimina a perda ao fazer cada incremento ocorrer em seccao critica
 		o lock serializa as atualizacoes e adiciona sobrecusto de sincronizacao, aumentando o tempo medio."
 	);
+
+	compare_lock_types(thread_count, workload);
+}
+
+struct LockRow {
+	name: &'static str,
+	threads: usize,
+	avg_seconds: f64,
+	is_correct: bool,
+}
+
+fn lock_thread_counts(max_threads: usize) -> Vec<usize> {
+	// Potencias de dois ate o maximo informado, incluindo o proprio maximo.
+	let mut counts: Vec<usize> = std::iter::successors(Some(1usize), |&count| count.checked_mul(2))
+		.take_while(|&count| count < max_threads)
+		.collect();
+	counts.push(max_threads);
+	counts
+}
+
+fn measure_lock<L: RawLock>(threads: usize, workload: Workload) -> LockRow {
+	let (avg, _, outputs) =
+		measure_runs(|_| raw_locked_counter(Arc::new(L::with_threads(threads)), threads, workload, false));
+	let expected = threads * workload.iterations;
+	LockRow {
+		name: L::NAME,
+		threads,
+		avg_seconds: avg,
+		is_correct: outputs.iter().all(|&total| total == expected),
+	}
+}
+
+fn compare_lock_types(max_threads: usize, workload: Workload) {
+	let thread_counts = lock_thread_counts(max_threads);
+	println!("\nComparacao de implementacoes de trava (threads {:?}):", thread_counts);
+	println!(
+		"Obs.: a tabela usa a versao generica (atomico sob a trava, yield fora dela); a linha std::sync::Mutex \
+	nao repete o tempo \"com trava\" acima, que guarda um Mutex<usize> e cede a CPU com a trava em posse."
+	);
+
+	let mut rows = Vec::new();
+	for &threads in &thread_counts {
+		rows.push(measure_lock::<StdMutex>(threads, workload));
+		rows.push(measure_lock::<TasSpinLock>(threads, workload));
+		rows.push(measure_lock::<TtasBackoffLock>(threads, workload));
+		rows.push(measure_lock::<TicketLock>(threads, workload));
+		rows.push(measure_lock::<McsLock>(threads, workload));
+		rows.push(measure_lock::<FutexMutex>(threads, workload));
+	}
+
+	println!("Trava            | Threads | Tempo (ms) | ns/incremento | vs std::Mutex | Corretude");
+	for row in &rows {
+		let baseline = rows
+			.iter()
+			.find(|other| other.threads == row.threads && other.name == StdMutex::NAME)
+			.expect("Linha base std::sync::Mutex ausente");
+		let increments = (row.threads * workload.iterations) as f64;
+		println!(
+			"{:<16} | {:>7} | {:>10.3} | {:>13.2} | {:>12.3}x | {}",
+			row.name,
+			row.threads,
+			row.avg_seconds * 1_000.0,
+			row.avg_seconds * 1e9 / increments,
+			row.avg_seconds / baseline.avg_seconds,
+			if row.is_correct { "OK" } else { "FALHOU" }
+		);
+	}
+	println!(
+		"Leitura: spinlocks evitam o kernel mas desperdicam ciclos quando ha mais threads que nucleos; \
+	filas (ticket, MCS) garantem ordem FIFO, e o futex dorme no kernel apenas quando ha disputa."
+	);
 }
 
 fn read_thread_count() -> Result<usize, String> {
@@ -77,6 +187,40 @@ fn read_thread_count() -> Result<usize, String> {
 		.map_err(|_| format!("Entrada invalida para threads: {}", input.trim()))
 }
 
+// No modo medir, dois numeros opcionais apos o modo: ns de trabalho dentro e fora da secao critica.
+fn read_workload(calibration: &SpinCalibration) -> Result<Workload, String> {
+	let read_ns = |position: usize, label: &str| -> Result<u64, String> {
+		match env::args().nth(position) {
+			Some(arg) => arg
+				.parse::<u64>()
+				.map_err(|_| format!("Argumento invalido para {}: {}", label, arg)),
+			None => Ok(0),
+		}
+	};
+	let critical_ns = read_ns(3, "ns na secao critica")?;
+	let outside_ns = read_ns(4, "ns fora da secao critica")?;
+
+	if critical_ns == 0 && outside_ns == 0 {
+		return Ok(Workload::plain(ITERATIONS_PER_THREAD));
+	}
+	Ok(Workload::with_work(ITERATIONS_PER_THREAD, critical_ns, outside_ns, calibration))
+}
+
+enum Mode {
+	Measure,
+	Contention,
+	Poisoning,
+}
+
+fn read_mode() -> Result<Mode, String> {
+	match env::args().nth(2).as_deref() {
+		None | Some("medir") => Ok(Mode::Measure),
+		Some("contencao") => Ok(Mode::Contention),
+		Some("envenenamento") => Ok(Mode::Poisoning),
+		Some(other) => Err(format!("Modo desconhecido: {} (use medir, contencao ou envenenamento)", other)),
+	}
+}
+
 fn measure_runs<F>(mut job: F) -> (f64, Vec<Duration>, Vec<usize>)
 where
 	F: FnMut(usize) -> usize,
@@ -111,15 +255,18 @@ fn log_durations(durations: &[Duration]) {
 	println!("  Obs.: primeira execucao funciona como aquecimento.");
 }
 
-fn race_condition_counter(thread_count: usize, should_print: bool) -> usize {
+fn race_condition_counter(thread_count: usize, workload: Workload, should_print: bool) -> usize {
 	let counter = Arc::new(AtomicUsize::new(0));
 	let mut handles = Vec::with_capacity(thread_count);
 
 	for thread_id in 0..thread_count {
 		let counter_clone = Arc::clone(&counter);
 		handles.push(thread::spawn(move || {
-			for iter in 0..ITERATIONS_PER_THREAD {
+			for iter in 0..workload.iterations {
+				workload.outside();
 				let current = counter_clone.load(Ordering::Relaxed);
+				// Sem trava, o trabalho "critico" so alarga a janela entre o load e o store.
+				workload.critical();
 				// Atualizacao nao atomica (load + store) que causa perda quando outras threads escrevem entre as operacoes.
 				counter_clone.store(current + 1, Ordering::Relaxed);
 				if iter % 1024 == 0 {
@@ -139,16 +286,18 @@ fn race_condition_counter(thread_count: usize, should_print: bool) -> usize {
 	counter.load(Ordering::Relaxed)
 }
 
-fn locked_counter(thread_count: usize, should_print: bool) -> usize {
+fn locked_counter(thread_count: usize, workload: Workload, should_print: bool) -> usize {
 	let counter = Arc::new(Mutex::new(0usize));
 	let mut handles = Vec::with_capacity(thread_count);
 
 	for thread_id in 0..thread_count {
 		let counter_clone = Arc::clone(&counter);
 		handles.push(thread::spawn(move || {
-			for iter in 0..ITERATIONS_PER_THREAD {
+			for iter in 0..workload.iterations {
+				workload.outside();
 				let mut guard = counter_clone.lock().expect("Mutex poisoned");
 				// Exclusao mutua garante que apenas uma thread altera o contador por vez.
+				workload.critical();
 				*guard += 1;
 				if iter % 1024 == 0 {
 					thread::yield_now();
@@ -168,11 +317,98 @@ fn locked_counter(thread_count: usize, should_print: bool) -> usize {
 	*guard
 }
 
-fn sequential_counter(thread_count: usize, should_print: bool) -> usize {
+// Versao generica usada na tabela de comparacao e no modo contencao. Difere do locked_counter acima:
+// a trava protege um atomico lido e escrito em separado e o yield ocorre fora da secao critica,
+// entao seus tempos nao se comparam diretamente com a medicao principal.
+fn raw_locked_counter<L: RawLock>(lock: Arc<L>, thread_count: usize, workload: Workload, should_print: bool) -> usize {
+	// O contador e um atomico lido e escrito separadamente: so fica correto se a trava excluir de fato.
+	let counter = Arc::new(AtomicUsize::new(0));
+	let mut handles = Vec::with_capacity(thread_count);
+
+	for thread_id in 0..thread_count {
+		let lock_clone = Arc::clone(&lock);
+		let counter_clone = Arc::clone(&counter);
+		handles.push(thread::spawn(move || {
+			for iter in 0..workload.iterations {
+				workload.outside();
+				lock_clone.with_lock(thread_id, || {
+					// Exclusao mutua garante que apenas uma thread altera o contador por vez.
+					workload.critical();
+					let current = counter_clone.load(Ordering::Relaxed);
+					counter_clone.store(current + 1, Ordering::Relaxed);
+				});
+				if iter % 1024 == 0 {
+					thread::yield_now();
+				}
+			}
+			if should_print {
+				println!("Thread {} finalizada (com trava {})", thread_id, L::NAME);
+			}
+		}));
+	}
+
+	for handle in handles {
+		handle.join().expect("Thread panicked during execution");
+	}
+
+	counter.load(Ordering::Relaxed)
+}
+
+fn run_contention_report(thread_count: usize) {
+	println!("Atividade 4 — Contencao da versao com trava");
+	println!(
+		"T = {} | cada thread incrementa {} vezes | Mutex instrumentado (espera, posse, disputa, justica)",
+		thread_count,
+		ITERATIONS_PER_THREAD
+	);
+
+	let lock = Arc::new(InstrumentedMutex::with_threads(thread_count));
+	let total = raw_locked_counter(Arc::clone(&lock), thread_count, Workload::plain(ITERATIONS_PER_THREAD), false);
+	println!("Valor final: {} (esperado {})", total, thread_count * ITERATIONS_PER_THREAD);
+
+	println!();
+	print_lock_reports(&[("com trava", lock.report())]);
+	println!(
+		"Leitura: aquisicoes disputadas sao as que encontraram a trava ocupada; a cauda da espera (p99/max) \
+	mostra quanto uma thread pode ficar parada quando o dono da trava e preemptado dentro da secao critica."
+	);
+}
+
+fn run_poisoning_report(thread_count: usize) {
+	println!("Atividade 4 — Envenenamento do Mutex");
+	println!("{}", poisoning::describe());
+	println!("O estado guarda o total e a contagem por thread; o panico ocorre entre as duas atualizacoes.");
+
+	let outcomes = run_policies(thread_count);
+
+	println!("\nPolitica               | Valor final | Esperado | Incrementos perdidos | Panicos | Reparos | Reinicios | Estado");
+	for outcome in &outcomes {
+		println!(
+			"{:<22} | {:>11} | {:>8} | {:>20} | {:>7} | {:>7} | {:>9} | {}",
+			outcome.policy.label(),
+			outcome.final_value,
+			outcome.expected,
+			outcome.lost_increments(),
+			outcome.panicked_threads,
+			outcome.repairs,
+			outcome.restarts,
+			if outcome.consistent { "consistente" } else { "INCONSISTENTE" }
+		);
+	}
+	println!(
+		"Leitura: com expect o veneno se propaga e derruba as demais threads; into_inner aproveita o dado \
+	mas exige validar e reparar o estado, e a thread que falhou nao volta; limpar o veneno e reiniciar \
+	o trabalhador a partir do progresso registrado recupera todos os incrementos."
+	);
+}
+
+fn sequential_counter(thread_count: usize, workload: Workload, should_print: bool) -> usize {
 	let mut counter = 0usize;
 
 	for worker in 0..thread_count {
-		for _ in 0..ITERATIONS_PER_THREAD {
+		for _ in 0..workload.iterations {
+			workload.outside();
+			workload.critical();
 			counter += 1;
 		}
 		if should_print {
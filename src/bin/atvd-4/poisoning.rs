@@ -0,0 +1,216 @@
+use std::panic;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const ITERATIONS: usize = 100_000;
+const FAILING_THREAD: usize = 0;
+const FAIL_AT: usize = ITERATIONS / 2;
+const THREAD_PREFIX: &str = "envenenamento-";
+const INJECTED_PANIC: &str = "Falha injetada com a trava do contador adquirida";
+
+// Estado protegido com redundancia: `value` deve ser sempre a soma de `per_thread`.
+// O panico injetado acontece entre as duas atualizacoes, deixando o estado inconsistente.
+struct CounterState {
+	value: usize,
+	per_thread: Vec<usize>,
+}
+
+impl CounterState {
+	fn is_consistent(&self) -> bool {
+		self.value == self.per_thread.iter().sum::<usize>()
+	}
+
+	fn repair(&mut self) -> bool {
+		if self.is_consistent() {
+			return false;
+		}
+		self.value = self.per_thread.iter().sum();
+		true
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PoisonPolicy {
+	Abort,
+	Recover,
+	Restart,
+}
+
+impl PoisonPolicy {
+	pub fn label(self) -> &'static str {
+		match self {
+			PoisonPolicy::Abort => "abortar (expect)",
+			PoisonPolicy::Recover => "recuperar (into_inner)",
+			PoisonPolicy::Restart => "limpar e reiniciar",
+		}
+	}
+}
+
+pub struct PoisonOutcome {
+	pub policy: PoisonPolicy,
+	pub final_value: usize,
+	pub expected: usize,
+	pub panicked_threads: usize,
+	pub repairs: usize,
+	pub restarts: usize,
+	pub consistent: bool,
+}
+
+impl PoisonOutcome {
+	pub fn lost_increments(&self) -> usize {
+		self.expected.saturating_sub(self.final_value)
+	}
+}
+
+pub fn run_policies(thread_count: usize) -> Vec<PoisonOutcome> {
+	// Silencia apenas os panicos das threads desta demonstracao; os demais seguem para o hook padrao.
+	let default_hook = Arc::new(panic::take_hook());
+	let hook_fallback = Arc::clone(&default_hook);
+	panic::set_hook(Box::new(move |info| {
+		let is_demo = thread::current()
+			.name()
+			.is_some_and(|name| name.starts_with(THREAD_PREFIX));
+		if !is_demo {
+			hook_fallback(info);
+		}
+	}));
+
+	let threads = thread_count.max(2);
+	let outcomes = [PoisonPolicy::Abort, PoisonPolicy::Recover, PoisonPolicy::Restart]
+		.into_iter()
+		.map(|policy| run_policy(policy, threads))
+		.collect();
+
+	drop(panic::take_hook());
+	let default_hook = Arc::try_unwrap(default_hook).unwrap_or_else(|_| panic!("Hook padrao ainda compartilhado"));
+	panic::set_hook(default_hook);
+
+	outcomes
+}
+
+fn lock_with_policy<'a>(
+	counter: &'a Mutex<CounterState>,
+	policy: PoisonPolicy,
+	repairs: &Mutex<usize>,
+) -> MutexGuard<'a, CounterState> {
+	match policy {
+		// Comportamento atual do projeto: a trava envenenada derruba quem a tocar.
+		PoisonPolicy::Abort => counter.lock().expect("Mutex poisoned"),
+		PoisonPolicy::Recover => counter.lock().unwrap_or_else(|poisoned| {
+			let mut guard = poisoned.into_inner();
+			if guard.repair() {
+				*repairs.lock().expect("Mutex poisoned") += 1;
+			}
+			guard
+		}),
+		// O supervisor limpa o veneno; ate la os trabalhadores apenas aguardam.
+		PoisonPolicy::Restart => loop {
+			match counter.lock() {
+				Ok(guard) => break guard,
+				Err(_) => thread::sleep(Duration::from_micros(50)),
+			}
+		},
+	}
+}
+
+struct Shared {
+	counter: Mutex<CounterState>,
+	repairs: Mutex<usize>,
+}
+
+fn spawn_worker(
+	shared: &Arc<Shared>,
+	policy: PoisonPolicy,
+	thread_id: usize,
+	start_at: usize,
+	inject_failure: bool,
+) -> JoinHandle<()> {
+	let shared = Arc::clone(shared);
+	thread::Builder::new()
+		.name(format!("{}{}", THREAD_PREFIX, thread_id))
+		.spawn(move || {
+			for iter in start_at..ITERATIONS {
+				let mut guard = lock_with_policy(&shared.counter, policy, &shared.repairs);
+				guard.per_thread[thread_id] += 1;
+				if inject_failure && iter == FAIL_AT {
+					panic!("{}", INJECTED_PANIC);
+				}
+				guard.value += 1;
+				drop(guard);
+				if iter % 1024 == 0 {
+					thread::yield_now();
+				}
+			}
+		})
+		.expect("Falha ao criar thread")
+}
+
+fn run_policy(policy: PoisonPolicy, threads: usize) -> PoisonOutcome {
+	let shared = Arc::new(Shared {
+		counter: Mutex::new(CounterState {
+			value: 0,
+			per_thread: vec![0; threads],
+		}),
+		repairs: Mutex::new(0),
+	});
+
+	let mut handles: Vec<(usize, JoinHandle<()>)> = (0..threads)
+		.map(|thread_id| (thread_id, spawn_worker(&shared, policy, thread_id, 0, thread_id == FAILING_THREAD)))
+		.collect();
+
+	let mut panicked_threads = 0;
+	let mut restarts = 0;
+	while !handles.is_empty() {
+		if policy == PoisonPolicy::Restart && shared.counter.is_poisoned() {
+			// Supervisor: repara o estado, limpa o veneno e deixa os demais seguirem.
+			let mut guard = shared.counter.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+			if guard.repair() {
+				*shared.repairs.lock().expect("Mutex poisoned") += 1;
+			}
+			shared.counter.clear_poison();
+		}
+
+		let (finished, running): (Vec<_>, Vec<_>) =
+			handles.into_iter().partition(|(_, handle)| handle.is_finished());
+		handles = running;
+
+		for (thread_id, handle) in finished {
+			if handle.join().is_ok() {
+				continue;
+			}
+			panicked_threads += 1;
+			if policy == PoisonPolicy::Restart {
+				// Reinicia a partir do progresso registrado no estado ja reparado.
+				let completed = shared
+					.counter
+					.lock()
+					.unwrap_or_else(|poisoned| poisoned.into_inner())
+					.per_thread[thread_id];
+				restarts += 1;
+				handles.push((thread_id, spawn_worker(&shared, policy, thread_id, completed, false)));
+			}
+		}
+
+		thread::sleep(Duration::from_micros(200));
+	}
+
+	let repairs = *shared.repairs.lock().expect("Mutex poisoned");
+	let state = shared.counter.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+	PoisonOutcome {
+		policy,
+		final_value: state.value,
+		expected: threads * ITERATIONS,
+		panicked_threads,
+		repairs,
+		restarts,
+		consistent: state.is_consistent(),
+	}
+}
+
+pub fn describe() -> String {
+	format!(
+		"Thread {} entra em panico na iteracao {} de {}, com a trava adquirida e o estado pela metade",
+		FAILING_THREAD, FAIL_AT, ITERATIONS
+	)
+}
@@ -0,0 +1,90 @@
+use std::hint;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, TryLockError};
+use std::thread;
+
+const SPIN_LIMIT: u32 = 64;
+
+// Slot de pedido por thread, em linha de cache propria: 0 = vazio, >0 = incremento pendente.
+#[repr(align(128))]
+struct RequestSlot {
+	pending: AtomicUsize,
+}
+
+struct CombinerState {
+	value: usize,
+	passes: usize,
+	applied: usize,
+}
+
+// Flat combining: cada thread publica o pedido no proprio slot e tenta a trava; quem consegue
+// vira combinador e aplica todos os pedidos pendentes de uma vez. As demais apenas observam o
+// proprio slot ate ele ser zerado, sem disputar a trava a cada incremento.
+pub struct FlatCombiningCounter {
+	state: Mutex<CombinerState>,
+	slots: Vec<RequestSlot>,
+}
+
+impl FlatCombiningCounter {
+	pub fn new(threads: usize) -> Self {
+		Self {
+			state: Mutex::new(CombinerState {
+				value: 0,
+				passes: 0,
+				applied: 0,
+			}),
+			slots: (0..threads)
+				.map(|_| RequestSlot {
+					pending: AtomicUsize::new(0),
+				})
+				.collect(),
+		}
+	}
+
+	pub fn add(&self, thread_id: usize, delta: usize) {
+		let slot = &self.slots[thread_id];
+		slot.pending.store(delta, Ordering::Release);
+
+		let mut spins = 0;
+		loop {
+			if slot.pending.load(Ordering::Acquire) == 0 {
+				// Outro combinador ja aplicou nosso pedido.
+				return;
+			}
+			match self.state.try_lock() {
+				Ok(mut state) => {
+					state.passes += 1;
+					for other in &self.slots {
+						let request = other.pending.swap(0, Ordering::AcqRel);
+						if request > 0 {
+							state.value += request;
+							state.applied += 1;
+						}
+					}
+					return;
+				}
+				Err(TryLockError::WouldBlock) => {}
+				Err(TryLockError::Poisoned(_)) => panic!("Mutex poisoned"),
+			}
+			if spins < SPIN_LIMIT {
+				spins += 1;
+				hint::spin_loop();
+			} else {
+				thread::yield_now();
+			}
+		}
+	}
+
+	pub fn value(&self) -> usize {
+		self.state.lock().expect("Mutex poisoned").value
+	}
+
+	// Pedidos aplicados por passada de combinacao: 1.0 = nenhuma combinacao aconteceu.
+	pub fn combining_degree(&self) -> f64 {
+		let state = self.state.lock().expect("Mutex poisoned");
+		if state.passes == 0 {
+			return 0.0;
+		}
+		state.applied as f64 / state.passes as f64
+	}
+}
@@ -0,0 +1,182 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Instant;
+
+// Mutex que mede cada aquisicao: tempo de espera, tempo com a trava e se houve disputa
+// (try_lock falhou antes do lock bloqueante). Cada thread grava apenas no proprio slot;
+// thread_id fora do intervalo (por exemplo a main lendo o total) nao entra nas estatisticas.
+pub struct InstrumentedMutex<T> {
+	inner: Mutex<T>,
+	per_thread: Vec<Mutex<ThreadLockStats>>,
+}
+
+#[derive(Default)]
+struct ThreadLockStats {
+	acquisitions: usize,
+	contended: usize,
+	wait_ns: Vec<u32>,
+	total_wait_ns: u64,
+	total_hold_ns: u64,
+}
+
+pub struct InstrumentedGuard<'a, T> {
+	guard: Option<MutexGuard<'a, T>>,
+	owner: &'a InstrumentedMutex<T>,
+	thread_id: usize,
+	acquired_at: Instant,
+	wait_ns: u64,
+	contended: bool,
+}
+
+impl<T> InstrumentedMutex<T> {
+	pub fn new(value: T, threads: usize) -> Self {
+		Self {
+			inner: Mutex::new(value),
+			per_thread: (0..threads).map(|_| Mutex::new(ThreadLockStats::default())).collect(),
+		}
+	}
+
+	pub fn lock(&self, thread_id: usize) -> InstrumentedGuard<'_, T> {
+		let requested_at = Instant::now();
+		let (guard, contended) = match self.inner.try_lock() {
+			Ok(guard) => (guard, false),
+			Err(_) => (self.inner.lock().expect("Mutex poisoned"), true),
+		};
+		let acquired_at = Instant::now();
+
+		InstrumentedGuard {
+			guard: Some(guard),
+			owner: self,
+			thread_id,
+			acquired_at,
+			wait_ns: acquired_at.duration_since(requested_at).as_nanos() as u64,
+			contended,
+		}
+	}
+
+	pub fn report(&self) -> LockReport {
+		let stats: Vec<_> = self
+			.per_thread
+			.iter()
+			.map(|slot| slot.lock().expect("Mutex poisoned"))
+			.collect();
+
+		let acquisitions: usize = stats.iter().map(|thread| thread.acquisitions).sum();
+		let contended: usize = stats.iter().map(|thread| thread.contended).sum();
+		let mut waits: Vec<u32> = stats.iter().flat_map(|thread| thread.wait_ns.iter().copied()).collect();
+		waits.sort_unstable();
+		let total_hold_ns: u64 = stats.iter().map(|thread| thread.total_hold_ns).sum();
+
+		let per_thread_wait: Vec<f64> = stats.iter().map(|thread| thread.total_wait_ns as f64).collect();
+
+		LockReport {
+			acquisitions,
+			contended,
+			wait_p50_ns: percentile(&waits, 0.50),
+			wait_p90_ns: percentile(&waits, 0.90),
+			wait_p99_ns: percentile(&waits, 0.99),
+			wait_max_ns: waits.last().copied().unwrap_or(0),
+			mean_hold_ns: if acquisitions == 0 {
+				0.0
+			} else {
+				total_hold_ns as f64 / acquisitions as f64
+			},
+			per_thread_acquisitions: stats.iter().map(|thread| thread.acquisitions).collect(),
+			jain_wait: jain_index(&per_thread_wait),
+		}
+	}
+}
+
+impl<T> Deref for InstrumentedGuard<'_, T> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		self.guard.as_ref().expect("Guard ja liberado")
+	}
+}
+
+impl<T> DerefMut for InstrumentedGuard<'_, T> {
+	fn deref_mut(&mut self) -> &mut T {
+		self.guard.as_mut().expect("Guard ja liberado")
+	}
+}
+
+impl<T> Drop for InstrumentedGuard<'_, T> {
+	fn drop(&mut self) {
+		let hold_ns = self.acquired_at.elapsed().as_nanos() as u64;
+		// Solta a trava antes de gravar, para a contabilidade nao inflar o tempo de posse.
+		drop(self.guard.take());
+		if let Some(slot) = self.owner.per_thread.get(self.thread_id) {
+			let mut stats = slot.lock().expect("Mutex poisoned");
+			stats.acquisitions += 1;
+			stats.contended += usize::from(self.contended);
+			stats.wait_ns.push(u32::try_from(self.wait_ns).unwrap_or(u32::MAX));
+			stats.total_wait_ns += self.wait_ns;
+			stats.total_hold_ns += hold_ns;
+		}
+	}
+}
+
+pub struct LockReport {
+	pub acquisitions: usize,
+	pub contended: usize,
+	pub wait_p50_ns: u32,
+	pub wait_p90_ns: u32,
+	pub wait_p99_ns: u32,
+	pub wait_max_ns: u32,
+	pub mean_hold_ns: f64,
+	pub per_thread_acquisitions: Vec<usize>,
+	pub jain_wait: f64,
+}
+
+impl LockReport {
+	pub fn contention_ratio(&self) -> f64 {
+		if self.acquisitions == 0 {
+			return 0.0;
+		}
+		self.contended as f64 / self.acquisitions as f64
+	}
+}
+
+fn percentile(sorted: &[u32], quantile: f64) -> u32 {
+	if sorted.is_empty() {
+		return 0;
+	}
+	let index = ((sorted.len() - 1) as f64 * quantile).round() as usize;
+	sorted[index]
+}
+
+// Indice de Jain: (soma x)^2 / (n * soma x^2); 1.0 = divisao perfeitamente igual, 1/n = uma thread leva tudo.
+fn jain_index(values: &[f64]) -> f64 {
+	let sum: f64 = values.iter().sum();
+	let sum_squares: f64 = values.iter().map(|value| value * value).sum();
+	if sum_squares <= f64::EPSILON {
+		return 1.0;
+	}
+	sum * sum / (values.len() as f64 * sum_squares)
+}
+
+pub fn print_lock_reports(reports: &[(&str, LockReport)]) {
+	println!(
+		"Variante               | Aquisicoes | Disputadas | Contencao | Espera p50/p90/p99/max (ns)      | Posse media (ns) | Jain espera"
+	);
+	for (label, report) in reports {
+		println!(
+			"{:<22} | {:>10} | {:>10} | {:>8.2}% | {:>7}/{:>7}/{:>7}/{:>9} | {:>16.1} | {:>11.4}",
+			label,
+			report.acquisitions,
+			report.contended,
+			report.contention_ratio() * 100.0,
+			report.wait_p50_ns,
+			report.wait_p90_ns,
+			report.wait_p99_ns,
+			report.wait_max_ns,
+			report.mean_hold_ns,
+			report.jain_wait
+		);
+	}
+	println!("Aquisicoes por thread:");
+	for (label, report) in reports {
+		println!("  {:<22} {:?}", label, report.per_thread_acquisitions);
+	}
+}
@@ -1,15 +1,35 @@
+mod combining;
+mod lock_stats;
+mod sharded;
+mod workload;
+
+use combining::FlatCombiningCounter;
+use lock_stats::{print_lock_reports, InstrumentedMutex};
+use sharded::ShardedCounter;
+use workload::{SpinCalibration, Workload};
 use std::env;
 use std::io::{self, Write};
-use std::sync::{Arc, Mutex};
+use std::ops::DerefMut;
+use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 
 const RUNS: usize = 5;
 const ITERATIONS_PER_THREAD: usize = 1_000_000;
 const BLOCK_SIZE: usize = 1_000;
+// Joelho da curva: menor bloco cujo tempo fica a ate 10% do patamar (mediana da metade com blocos maiores).
+const KNEE_TOLERANCE: f64 = 0.10;
+const CURVE_WIDTH: usize = 40;
+// Capacidade do canal da thread servidora: limita quantos pedidos podem ficar na fila.
+const SERVER_QUEUE_CAPACITY: usize = 1_024;
+// Mapa de calor: iteracoes reduzidas, pois cada celula soma trabalho ocupado por iteracao.
+const HEATMAP_ITERATIONS: usize = 20_000;
+const HEATMAP_CRITICAL_NS: [u64; 4] = [0, 100, 500, 2_000];
+const HEATMAP_OUTSIDE_NS: [u64; 4] = [0, 100, 1_000, 5_000];
+const HEATMAP_SHADES: [char; 6] = [' ', '.', ':', '+', '#', '@'];
 
 fn main() {
-	assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful");
+	const { assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful") };
 
 	let thread_count = read_thread_count().unwrap_or_else(|err| {
 		eprintln!("{}", err);
@@ -18,20 +38,61 @@ fn main() {
 
 	assert!(thread_count > 0, "Use um valor de threads maior que zero");
 
-	let expected_total = thread_count * ITERATIONS_PER_THREAD;
+	let mode = read_mode().unwrap_or_else(|err| {
+		eprintln!("{}", err);
+		std::process::exit(1);
+	});
+
+	match mode {
+		Mode::Measure => {}
+		Mode::Contention => {
+			run_contention_report(thread_count);
+			return;
+		}
+		Mode::BlockSweep => {
+			run_block_sweep(thread_count);
+			return;
+		}
+		Mode::Sharded => {
+			run_shard_comparison(thread_count);
+			return;
+		}
+		Mode::Combining => {
+			run_combining_comparison(thread_count);
+			return;
+		}
+		Mode::Heatmap => {
+			run_granularity_heatmap(thread_count);
+			return;
+		}
+	}
+
+	let calibration = SpinCalibration::measure();
+	let workload = read_workload(&calibration).unwrap_or_else(|err| {
+		eprintln!("{}", err);
+		std::process::exit(1);
+	});
+
+	let expected_total = thread_count * workload.iterations;
 
 	println!("Atividade 5 — Variando a granularidade do lock");
-	println!("Cada thread incrementa o contador {} vezes; valor esperado = {}", ITERATIONS_PER_THREAD, expected_total);
+	println!("Cada thread incrementa o contador {} vezes; valor esperado = {}", workload.iterations, expected_total);
+	println!(
+		"Trabalho por iteracao: {} ns na secao critica, {} ns fora dela (calibracao: {:.3} voltas/ns)",
+		workload.critical_ns,
+		workload.outside_ns,
+		calibration.spins_per_ns()
+	);
 	println!("Total de execucoes temporizadas: {} ({} entram na media)", RUNS, RUNS - 1);
 
 	let (per_increment_avg, per_increment_times, per_increment_outputs) =
-		measure_runs(|run| lock_each_increment(thread_count, run == 0));
+		measure_runs(|run| lock_each_increment(new_counter(), thread_count, workload, run == 0));
 	let (block_avg, block_times, block_outputs) =
-		measure_runs(|run| lock_in_blocks(thread_count, run == 0));
+		measure_runs(|run| lock_in_blocks(new_counter(), thread_count, BLOCK_SIZE, workload, run == 0));
 	let (single_avg, single_times, single_outputs) =
-		measure_runs(|run| lock_once(thread_count, run == 0));
+		measure_runs(|run| lock_once(new_counter(), thread_count, workload, run == 0));
 	let (sequential_avg, sequential_times, sequential_outputs) =
-		measure_runs(|run| sequential_counter(thread_count, run == 0));
+		measure_runs(|run| sequential_counter(thread_count, workload, run == 0));
 
 	let per_increment_final = *per_increment_outputs.last().unwrap_or(&0);
 	let block_final = *block_outputs.last().unwrap_or(&0);
@@ -96,6 +157,49 @@ fn read_thread_count() -> Result<usize, String> {
 		.map_err(|_| format!("Entrada invalida para threads: {}", input.trim()))
 }
 
+enum Mode {
+	Measure,
+	Contention,
+	BlockSweep,
+	Sharded,
+	Combining,
+	Heatmap,
+}
+
+fn read_mode() -> Result<Mode, String> {
+	match env::args().nth(2).as_deref() {
+		None | Some("medir") => Ok(Mode::Measure),
+		Some("contencao") => Ok(Mode::Contention),
+		Some("blocos") => Ok(Mode::BlockSweep),
+		Some("fatias") => Ok(Mode::Sharded),
+		Some("combinacao") => Ok(Mode::Combining),
+		Some("mapa") => Ok(Mode::Heatmap),
+		Some(other) => Err(format!(
+			"Modo desconhecido: {} (use medir, contencao, blocos, fatias, combinacao ou mapa)",
+			other
+		)),
+	}
+}
+
+// No modo medir, dois numeros opcionais apos o modo: ns de trabalho dentro e fora da secao critica.
+fn read_workload(calibration: &SpinCalibration) -> Result<Workload, String> {
+	let read_ns = |position: usize, label: &str| -> Result<u64, String> {
+		match env::args().nth(position) {
+			Some(arg) => arg
+				.parse::<u64>()
+				.map_err(|_| format!("Argumento invalido para {}: {}", label, arg)),
+			None => Ok(0),
+		}
+	};
+	let critical_ns = read_ns(3, "ns na secao critica")?;
+	let outside_ns = read_ns(4, "ns fora da secao critica")?;
+
+	if critical_ns == 0 && outside_ns == 0 {
+		return Ok(Workload::plain(ITERATIONS_PER_THREAD));
+	}
+	Ok(Workload::with_work(ITERATIONS_PER_THREAD, critical_ns, outside_ns, calibration))
+}
+
 fn measure_runs<F>(mut job: F) -> (f64, Vec<Duration>, Vec<usize>)
 where
 	F: FnMut(usize) -> usize,
@@ -129,16 +233,44 @@ fn log_durations(durations: &[Duration]) {
 	println!("  Obs.: primeira execucao funciona como aquecimento.");
 }
 
-fn lock_each_increment(thread_count: usize, should_print: bool) -> usize {
-	let counter = Arc::new(Mutex::new(0usize));
+// Trava do contador usada pelas variantes: o Mutex comum nas medicoes e o instrumentado no modo contencao.
+// `thread_id` identifica quem adquire; a main usa `thread_count` ao ler o total.
+trait CounterLock: Send + Sync + 'static {
+	fn acquire(&self, thread_id: usize) -> impl DerefMut<Target = usize> + '_;
+}
+
+impl CounterLock for Mutex<usize> {
+	fn acquire(&self, _thread_id: usize) -> impl DerefMut<Target = usize> + '_ {
+		self.lock().expect("Mutex poisoned")
+	}
+}
+
+impl CounterLock for InstrumentedMutex<usize> {
+	fn acquire(&self, thread_id: usize) -> impl DerefMut<Target = usize> + '_ {
+		self.lock(thread_id)
+	}
+}
+
+fn new_counter() -> Arc<Mutex<usize>> {
+	Arc::new(Mutex::new(0usize))
+}
+
+fn lock_each_increment<L: CounterLock>(
+	counter: Arc<L>,
+	thread_count: usize,
+	workload: Workload,
+	should_print: bool,
+) -> usize {
 	let mut handles = Vec::with_capacity(thread_count);
 
 	for thread_id in 0..thread_count {
 		let counter_clone = Arc::clone(&counter);
 		handles.push(thread::spawn(move || {
-			for iter in 0..ITERATIONS_PER_THREAD {
-				let mut guard = counter_clone.lock().expect("Mutex poisoned");
+			for iter in 0..workload.iterations {
+				workload.outside();
+				let mut guard = counter_clone.acquire(thread_id);
 				// Granularidade fina: cada incremento entra na secao critica.
+				workload.critical();
 				*guard += 1;
 				if iter % 1024 == 0 {
 					thread::yield_now();
@@ -154,24 +286,33 @@ fn lock_each_increment(thread_count: usize, should_print: bool) -> usize {
 		handle.join().expect("Thread panicked during execution");
 	}
 
-	let guard = counter.lock().expect("Mutex poisoned");
+	let guard = counter.acquire(thread_count);
 	*guard
 }
 
-fn lock_in_blocks(thread_count: usize, should_print: bool) -> usize {
-	let counter = Arc::new(Mutex::new(0usize));
+// Nas variantes em lote, o trabalho critico de cada incremento acumulado e feito na aquisicao
+// que publica o lote: menos aquisicoes, mas cada uma segura a trava por mais tempo.
+fn lock_in_blocks<L: CounterLock>(
+	counter: Arc<L>,
+	thread_count: usize,
+	block_size: usize,
+	workload: Workload,
+	should_print: bool,
+) -> usize {
 	let mut handles = Vec::with_capacity(thread_count);
 
 	for thread_id in 0..thread_count {
 		let counter_clone = Arc::clone(&counter);
 		handles.push(thread::spawn(move || {
 			let mut local_batch = 0usize;
-			for iter in 0..ITERATIONS_PER_THREAD {
+			for iter in 0..workload.iterations {
+				workload.outside();
 				local_batch += 1;
-				if local_batch == BLOCK_SIZE {
-					let mut guard = counter_clone.lock().expect("Mutex poisoned");
+				if local_batch == block_size {
+					let mut guard = counter_clone.acquire(thread_id);
 					// Travamento apenas quando o lote atinge o tamanho definido.
-					*guard += BLOCK_SIZE;
+					(0..block_size).for_each(|_| workload.critical());
+					*guard += block_size;
 					local_batch = 0;
 				}
 				if iter % 4096 == 0 {
@@ -179,7 +320,8 @@ fn lock_in_blocks(thread_count: usize, should_print: bool) -> usize {
 				}
 			}
 			if local_batch > 0 {
-				let mut guard = counter_clone.lock().expect("Mutex poisoned");
+				let mut guard = counter_clone.acquire(thread_id);
+				(0..local_batch).for_each(|_| workload.critical());
 				*guard += local_batch;
 			}
 			if should_print {
@@ -192,26 +334,27 @@ fn lock_in_blocks(thread_count: usize, should_print: bool) -> usize {
 		handle.join().expect("Thread panicked during execution");
 	}
 
-	let guard = counter.lock().expect("Mutex poisoned");
+	let guard = counter.acquire(thread_count);
 	*guard
 }
 
-fn lock_once(thread_count: usize, should_print: bool) -> usize {
-	let counter = Arc::new(Mutex::new(0usize));
+fn lock_once<L: CounterLock>(counter: Arc<L>, thread_count: usize, workload: Workload, should_print: bool) -> usize {
 	let mut handles = Vec::with_capacity(thread_count);
 
 	for thread_id in 0..thread_count {
 		let counter_clone = Arc::clone(&counter);
 		handles.push(thread::spawn(move || {
 			let mut local_total = 0usize;
-			for iter in 0..ITERATIONS_PER_THREAD {
+			for iter in 0..workload.iterations {
+				workload.outside();
 				local_total += 1;
 				if iter % 8192 == 0 {
 					thread::yield_now();
 				}
 			}
-			let mut guard = counter_clone.lock().expect("Mutex poisoned");
+			let mut guard = counter_clone.acquire(thread_id);
 			// Travamento unico por thread: acumula tudo localmente.
+			(0..local_total).for_each(|_| workload.critical());
 			*guard += local_total;
 			if should_print {
 				println!("Thread {} finalizada (lock unico)", thread_id);
@@ -223,15 +366,473 @@ fn lock_once(thread_count: usize, should_print: bool) -> usize {
 		handle.join().expect("Thread panicked during execution");
 	}
 
-	let guard = counter.lock().expect("Mutex poisoned");
+	let guard = counter.acquire(thread_count);
 	*guard
 }
 
-fn sequential_counter(thread_count: usize, should_print: bool) -> usize {
+fn sharded_counter(counter: Arc<ShardedCounter>, thread_count: usize, should_print: bool) -> usize {
+	let mut handles = Vec::with_capacity(thread_count);
+
+	for thread_id in 0..thread_count {
+		let counter_clone = Arc::clone(&counter);
+		handles.push(thread::spawn(move || {
+			for iter in 0..ITERATIONS_PER_THREAD {
+				// Mesma granularidade de lock_each_increment, mas so disputa com quem cai na mesma fatia.
+				counter_clone.add(thread_id, 1);
+				if iter % 1024 == 0 {
+					thread::yield_now();
+				}
+			}
+			if should_print {
+				println!("Thread {} finalizada (fatia {})", thread_id, thread_id % counter_clone.shard_count());
+			}
+		}));
+	}
+
+	for handle in handles {
+		handle.join().expect("Thread panicked during execution");
+	}
+
+	counter.sum()
+}
+
+fn flat_combining_counter(counter: Arc<FlatCombiningCounter>, thread_count: usize, should_print: bool) -> usize {
+	let mut handles = Vec::with_capacity(thread_count);
+
+	for thread_id in 0..thread_count {
+		let counter_clone = Arc::clone(&counter);
+		handles.push(thread::spawn(move || {
+			for iter in 0..ITERATIONS_PER_THREAD {
+				counter_clone.add(thread_id, 1);
+				if iter % 1024 == 0 {
+					thread::yield_now();
+				}
+			}
+			if should_print {
+				println!("Thread {} finalizada (flat combining)", thread_id);
+			}
+		}));
+	}
+
+	for handle in handles {
+		handle.join().expect("Thread panicked during execution");
+	}
+
+	counter.value()
+}
+
+fn delegated_counter(thread_count: usize, should_print: bool) -> usize {
+	// Delegacao: so a thread servidora toca no contador; as demais apenas enviam pedidos.
+	let (sender, receiver) = mpsc::sync_channel::<usize>(SERVER_QUEUE_CAPACITY);
+	let server = thread::spawn(move || {
+		let mut counter = 0usize;
+		for delta in receiver {
+			counter += delta;
+		}
+		counter
+	});
+
+	let mut handles = Vec::with_capacity(thread_count);
+	for thread_id in 0..thread_count {
+		let sender_clone = sender.clone();
+		handles.push(thread::spawn(move || {
+			for iter in 0..ITERATIONS_PER_THREAD {
+				sender_clone.send(1).expect("Servidor encerrou antes dos clientes");
+				if iter % 1024 == 0 {
+					thread::yield_now();
+				}
+			}
+			if should_print {
+				println!("Thread {} finalizada (thread servidora)", thread_id);
+			}
+		}));
+	}
+	// Sem este drop o servidor nunca veria o canal fechar.
+	drop(sender);
+
+	for handle in handles {
+		handle.join().expect("Thread panicked during execution");
+	}
+
+	server.join().expect("Thread panicked during execution")
+}
+
+type InstrumentedVariant = fn(Arc<InstrumentedMutex<usize>>, usize, bool) -> usize;
+
+fn run_contention_report(thread_count: usize) {
+	println!("Atividade 5 — Contencao das travas por granularidade");
+	println!(
+		"T = {} | cada thread incrementa {} vezes | Mutex instrumentado (espera, posse, disputa, justica)",
+		thread_count,
+		ITERATIONS_PER_THREAD
+	);
+
+	let expected_total = thread_count * ITERATIONS_PER_THREAD;
+	let variants: [(&str, InstrumentedVariant); 3] = [
+		("lock a cada incremento", |counter, threads, should_print| {
+			lock_each_increment(counter, threads, Workload::plain(ITERATIONS_PER_THREAD), should_print)
+		}),
+		("lock por bloco", |counter, threads, should_print| {
+			lock_in_blocks(counter, threads, BLOCK_SIZE, Workload::plain(ITERATIONS_PER_THREAD), should_print)
+		}),
+		("lock unico", |counter, threads, should_print| {
+			lock_once(counter, threads, Workload::plain(ITERATIONS_PER_THREAD), should_print)
+		}),
+	];
+
+	let mut reports = Vec::with_capacity(variants.len());
+	for (label, variant) in variants {
+		let counter = Arc::new(InstrumentedMutex::new(0usize, thread_count));
+		let total = variant(Arc::clone(&counter), thread_count, false);
+		if total != expected_total {
+			println!("Aviso: {} produziu {} (esperado {})", label, total, expected_total);
+		}
+		reports.push((label, counter.report()));
+	}
+
+	println!();
+	print_lock_reports(&reports);
+	println!(
+		"Leitura: contencao = fracao de aquisicoes em que try_lock falhou; travar a cada incremento multiplica as aquisicoes \
+	e a espera, enquanto blocos e lock unico deixam a trava quase sempre livre. Jain da espera proximo de 1 indica que nenhuma thread esperou bem mais que as outras."
+	);
+}
+
+fn sweep_thread_counts(max_threads: usize) -> Vec<usize> {
+	// Potencias de dois ate o maximo informado, incluindo o proprio maximo.
+	let mut counts: Vec<usize> = std::iter::successors(Some(1usize), |&count| count.checked_mul(2))
+		.take_while(|&count| count < max_threads)
+		.collect();
+	counts.push(max_threads);
+	counts
+}
+
+fn sweep_block_sizes() -> Vec<usize> {
+	// Escala logaritmica: potencias de dois de 1 ate ITERATIONS_PER_THREAD (um unico lote).
+	let mut sizes: Vec<usize> = std::iter::successors(Some(1usize), |&size| size.checked_mul(2))
+		.take_while(|&size| size < ITERATIONS_PER_THREAD)
+		.collect();
+	sizes.push(ITERATIONS_PER_THREAD);
+	sizes
+}
+
+// Aquisicoes da trava por thread: um lote cheio por vez, mais o resto (sem contar a leitura final da main).
+fn block_acquisitions(block_size: usize) -> usize {
+	ITERATIONS_PER_THREAD.div_ceil(block_size)
+}
+
+struct SweepPoint {
+	block_size: usize,
+	avg_seconds: f64,
+}
+
+fn find_knee(points: &[SweepPoint]) -> &SweepPoint {
+	// A mediana do patamar resiste melhor a ruido do que o menor tempo isolado.
+	let mut plateau: Vec<f64> = points[points.len() / 2..]
+		.iter()
+		.map(|point| point.avg_seconds)
+		.collect();
+	plateau.sort_by(f64::total_cmp);
+	let plateau_seconds = plateau[plateau.len() / 2];
+	points
+		.iter()
+		.find(|point| point.avg_seconds <= plateau_seconds * (1.0 + KNEE_TOLERANCE))
+		.expect("Varredura sem pontos")
+}
+
+fn run_block_sweep(max_threads: usize) {
+	let thread_counts = sweep_thread_counts(max_threads);
+	let block_sizes = sweep_block_sizes();
+
+	println!("Atividade 5 — Varredura do tamanho de bloco");
+	println!(
+		"Cada thread incrementa {} vezes; blocos {:?}; threads {:?}",
+		ITERATIONS_PER_THREAD,
+		block_sizes,
+		thread_counts
+	);
+	println!("Total de execucoes temporizadas por ponto: {} ({} entram na media)", RUNS, RUNS - 1);
+
+	let mut knees = Vec::with_capacity(thread_counts.len());
+	for &threads in &thread_counts {
+		let expected_total = threads * ITERATIONS_PER_THREAD;
+		let workload = Workload::plain(ITERATIONS_PER_THREAD);
+		let points: Vec<SweepPoint> = block_sizes
+			.iter()
+			.map(|&block_size| {
+				let (avg, _, outputs) =
+					measure_runs(|_| lock_in_blocks(new_counter(), threads, block_size, workload, false));
+				if outputs.iter().any(|&total| total != expected_total) {
+					println!("Aviso: bloco {} produziu total incorreto com {} threads", block_size, threads);
+				}
+				SweepPoint {
+					block_size,
+					avg_seconds: avg,
+				}
+			})
+			.collect();
+
+		let slowest = points
+			.iter()
+			.map(|point| point.avg_seconds)
+			.fold(0.0, f64::max);
+		let knee = find_knee(&points);
+
+		println!("\n=== T = {} ===", threads);
+		println!("Bloco     | Aquisicoes | Tempo (ms) | vs anterior | Curva");
+		for (index, point) in points.iter().enumerate() {
+			let previous = if index == 0 {
+				String::from("-")
+			} else {
+				format!("{:+.1}%", percentage_change(points[index - 1].avg_seconds, point.avg_seconds))
+			};
+			let bar = ((point.avg_seconds / slowest) * CURVE_WIDTH as f64).round() as usize;
+			println!(
+				"{:>9} | {:>10} | {:>10.3} | {:>11} | {}{}",
+				point.block_size,
+				threads * block_acquisitions(point.block_size),
+				point.avg_seconds * 1_000.0,
+				previous,
+				"#".repeat(bar.max(1)),
+				if std::ptr::eq(point, knee) { "  <- joelho" } else { "" }
+			);
+		}
+
+		knees.push((threads, knee.block_size, knee.avg_seconds, points[0].avg_seconds));
+	}
+
+	println!("\nResumo do joelho (menor bloco a ate {:.0}% do patamar):", KNEE_TOLERANCE * 100.0);
+	println!("Threads | Bloco no joelho | Tempo no joelho (ms) | Bloco 1 (ms) | Ganho ate o joelho");
+	for (threads, block_size, knee_seconds, first_seconds) in &knees {
+		println!(
+			"{:>7} | {:>15} | {:>20.3} | {:>12.3} | {:>17.1}x",
+			threads,
+			block_size,
+			knee_seconds * 1_000.0,
+			first_seconds * 1_000.0,
+			first_seconds / knee_seconds
+		);
+	}
+
+	println!("\nAquisicoes da trava por thread em cada variante:");
+	println!("  lock a cada incremento: {}", ITERATIONS_PER_THREAD);
+	println!("  lock por bloco de {}: {}", BLOCK_SIZE, block_acquisitions(BLOCK_SIZE));
+	println!("  lock unico: 1");
+	println!(
+		"Leitura: o tempo cai quase na proporcao das aquisicoes enquanto o custo da trava domina; \
+	depois do joelho o laco local e que domina e blocos maiores nao pagam mais nada."
+	);
+}
+
+fn shard_counts(thread_count: usize) -> Vec<usize> {
+	// Potencias de dois ate 2T, incluindo T e 2T: acima de T sobram fatias que ninguem usa.
+	let limit = thread_count * 2;
+	let mut counts: Vec<usize> = std::iter::successors(Some(1usize), |&count| count.checked_mul(2))
+		.take_while(|&count| count < limit)
+		.collect();
+	counts.push(thread_count);
+	counts.push(limit);
+	counts.sort_unstable();
+	counts.dedup();
+	counts
+}
+
+fn run_shard_comparison(thread_count: usize) {
+	let expected_total = thread_count * ITERATIONS_PER_THREAD;
+	let shard_options = shard_counts(thread_count);
+
+	println!("Atividade 5 — Contador listrado (sharded)");
+	println!(
+		"T = {} | cada thread incrementa {} vezes | fatias avaliadas {:?}",
+		thread_count,
+		ITERATIONS_PER_THREAD,
+		shard_options
+	);
+	println!("Total de execucoes temporizadas: {} ({} entram na media)", RUNS, RUNS - 1);
+
+	let all_correct = |outputs: &[usize]| outputs.iter().all(|&total| total == expected_total);
+	let workload = Workload::plain(ITERATIONS_PER_THREAD);
+	let mut rows: Vec<(String, f64, bool)> = Vec::new();
+
+	let (per_increment_avg, _, outputs) =
+		measure_runs(|_| lock_each_increment(new_counter(), thread_count, workload, false));
+	rows.push((String::from("lock a cada incremento"), per_increment_avg, all_correct(&outputs)));
+	let (block_avg, _, outputs) =
+		measure_runs(|_| lock_in_blocks(new_counter(), thread_count, BLOCK_SIZE, workload, false));
+	rows.push((format!("lock por bloco de {}", BLOCK_SIZE), block_avg, all_correct(&outputs)));
+	let (single_avg, _, outputs) = measure_runs(|_| lock_once(new_counter(), thread_count, workload, false));
+	rows.push((String::from("lock unico"), single_avg, all_correct(&outputs)));
+
+	for &shards in &shard_options {
+		let (avg, _, outputs) =
+			measure_runs(|_| sharded_counter(Arc::new(ShardedCounter::new(shards)), thread_count, false));
+		rows.push((format!("listrado S = {}", shards), avg, all_correct(&outputs)));
+	}
+
+	println!("\nVariante                  | Tempo (ms) | vs lock a cada inc. | Corretude");
+	for (label, avg, is_correct) in &rows {
+		println!(
+			"{:<25} | {:>10.3} | {:>18.3}x | {}",
+			label,
+			avg * 1_000.0,
+			per_increment_avg / avg,
+			if *is_correct { "OK" } else { "FALHOU" }
+		);
+	}
+	println!(
+		"Leitura: o listrado mantem um lock por incremento, mas divide a disputa por S; o ganho cresce ate S = T \
+	(cada thread com a propria fatia) e para ai. Blocos e lock unico ainda vencem por quase nao travar, \
+	mas exigem acumular localmente, enquanto o listrado permite ler um total consistente a qualquer momento via sum()."
+	);
+}
+
+fn run_combining_comparison(max_threads: usize) {
+	let thread_counts = sweep_thread_counts(max_threads);
+
+	println!("Atividade 5 — Flat combining e thread servidora");
+	println!(
+		"Cada thread incrementa {} vezes, um pedido por incremento (contencao maxima); threads {:?}",
+		ITERATIONS_PER_THREAD,
+		thread_counts
+	);
+	println!("Total de execucoes temporizadas: {} ({} entram na media)", RUNS, RUNS - 1);
+
+	println!("\nThreads | Variante               | Tempo (ms) | ns/incremento | vs lock a cada inc. | Pedidos/passada | Corretude");
+	for &threads in &thread_counts {
+		let expected_total = threads * ITERATIONS_PER_THREAD;
+		let all_correct = |outputs: &[usize]| outputs.iter().all(|&total| total == expected_total);
+
+		let (baseline_avg, _, baseline_outputs) = measure_runs(|_| {
+			lock_each_increment(new_counter(), threads, Workload::plain(ITERATIONS_PER_THREAD), false)
+		});
+
+		let mut degrees = Vec::with_capacity(RUNS);
+		let (combining_avg, _, combining_outputs) = measure_runs(|_| {
+			let counter = Arc::new(FlatCombiningCounter::new(threads));
+			let total = flat_combining_counter(Arc::clone(&counter), threads, false);
+			degrees.push(counter.combining_degree());
+			total
+		});
+		let combining_degree = degrees.iter().skip(1).sum::<f64>() / (RUNS - 1) as f64;
+
+		let (server_avg, _, server_outputs) = measure_runs(|_| delegated_counter(threads, false));
+
+		let rows = [
+			("lock a cada incremento", baseline_avg, None, all_correct(&baseline_outputs)),
+			("flat combining", combining_avg, Some(combining_degree), all_correct(&combining_outputs)),
+			("thread servidora", server_avg, None, all_correct(&server_outputs)),
+		];
+		for (label, avg, degree, is_correct) in rows {
+			println!(
+				"{:>7} | {:<22} | {:>10.3} | {:>13.2} | {:>18.3}x | {:>15} | {}",
+				threads,
+				label,
+				avg * 1_000.0,
+				avg * 1e9 / expected_total as f64,
+				baseline_avg / avg,
+				degree.map_or(String::from("-"), |value| format!("{:.2}", value)),
+				if is_correct { "OK" } else { "FALHOU" }
+			);
+		}
+	}
+	println!(
+		"Leitura: no flat combining a trava e disputada por um combinador por vez e, com threads realmente simultaneas, \
+	cada passada aplica varios pedidos (Pedidos/passada > 1), reduzindo a troca da linha de cache do contador; \
+	com um unico nucleo quase nunca ha pedidos pendentes ao mesmo tempo e o valor fica perto de 1. A thread servidora elimina a trava, \
+	mas paga o custo do canal por pedido e depende de o servidor conseguir nucleo para drenar a fila."
+	);
+}
+
+fn run_granularity_heatmap(thread_count: usize) {
+	let calibration = SpinCalibration::measure();
+
+	println!("Atividade 5 — Mapa de calor da granularidade");
+	println!(
+		"T = {} | {} iteracoes por thread | calibracao: {:.3} voltas/ns",
+		thread_count,
+		HEATMAP_ITERATIONS,
+		calibration.spins_per_ns()
+	);
+	println!("Linhas: ns de trabalho na secao critica; colunas: ns de trabalho fora dela.");
+	println!("Total de execucoes temporizadas por celula: {} ({} entram na media)", RUNS, RUNS - 1);
+
+	let expected_total = thread_count * HEATMAP_ITERATIONS;
+	let mut grid = Vec::with_capacity(HEATMAP_CRITICAL_NS.len());
+	for &critical_ns in &HEATMAP_CRITICAL_NS {
+		let mut row = Vec::with_capacity(HEATMAP_OUTSIDE_NS.len());
+		for &outside_ns in &HEATMAP_OUTSIDE_NS {
+			let workload = Workload::with_work(HEATMAP_ITERATIONS, critical_ns, outside_ns, &calibration);
+			let (fine_avg, _, fine_outputs) =
+				measure_runs(|_| lock_each_increment(new_counter(), thread_count, workload, false));
+			let (block_avg, _, block_outputs) =
+				measure_runs(|_| lock_in_blocks(new_counter(), thread_count, BLOCK_SIZE, workload, false));
+			let (single_avg, _, single_outputs) =
+				measure_runs(|_| lock_once(new_counter(), thread_count, workload, false));
+
+			let all_correct = [&fine_outputs, &block_outputs, &single_outputs]
+				.iter()
+				.all(|outputs| outputs.iter().all(|&total| total == expected_total));
+			if !all_correct {
+				println!("Aviso: total incorreto com {} ns dentro e {} ns fora", critical_ns, outside_ns);
+			}
+			row.push([fine_avg, block_avg, single_avg]);
+		}
+		grid.push(row);
+	}
+
+	// Celula: vencedora (F = lock a cada incremento, B = bloco, U = lock unico) e quanto a
+	// granularidade fina custa em relacao a ela; a sombra cresce com esse custo (escala log2).
+	println!("\nVencedora e custo do lock fino (tempo fino / tempo da vencedora):");
+	print!("{:>14}", "dentro \\ fora");
+	for outside_ns in HEATMAP_OUTSIDE_NS {
+		print!(" | {:>12}", format!("{} ns", outside_ns));
+	}
+	println!();
+	for (critical_ns, row) in HEATMAP_CRITICAL_NS.iter().zip(&grid) {
+		print!("{:>14}", format!("{} ns", critical_ns));
+		for times in row {
+			let (winner, best) = times
+				.iter()
+				.enumerate()
+				.min_by(|left, right| left.1.total_cmp(right.1))
+				.map(|(index, &time)| (["F", "B", "U"][index], time))
+				.expect("Celula sem tempos");
+			let fine_cost = times[0] / best;
+			let shade_index = (fine_cost.log2().max(0.0).round() as usize).min(HEATMAP_SHADES.len() - 1);
+			let shade = HEATMAP_SHADES[shade_index].to_string().repeat(3);
+			print!(" | {} {:>5.2}x {}", winner, fine_cost, shade);
+		}
+		println!();
+	}
+	println!("Sombras: ' ' ate ~1.4x, '.' ~2x, ':' ~4x, '+' ~8x, '#' ~16x, '@' 32x ou mais.");
+
+	println!("\nTempos absolutos (ms) fino / bloco / unico:");
+	for (critical_ns, row) in HEATMAP_CRITICAL_NS.iter().zip(&grid) {
+		for (outside_ns, times) in HEATMAP_OUTSIDE_NS.iter().zip(row) {
+			println!(
+				"  dentro {:>5} ns | fora {:>5} ns | {:>9.3} / {:>9.3} / {:>9.3}",
+				critical_ns,
+				outside_ns,
+				times[0] * 1_000.0,
+				times[1] * 1_000.0,
+				times[2] * 1_000.0
+			);
+		}
+	}
+	println!(
+		"Leitura: com secao critica vazia e sem trabalho fora, o custo da trava domina e o lock fino perde por muito; \
+	conforme cresce o trabalho fora da secao critica a trava passa a ser uma fracao pequena de cada iteracao e a \
+	diferenca some. Trabalho critico longo e serializado em qualquer granularidade, entao tambem aproxima as variantes."
+	);
+}
+
+fn sequential_counter(thread_count: usize, workload: Workload, should_print: bool) -> usize {
 	let mut counter = 0usize;
 
 	for worker in 0..thread_count {
-		for _ in 0..ITERATIONS_PER_THREAD {
+		for _ in 0..workload.iterations {
+			workload.outside();
+			workload.critical();
 			counter += 1;
 		}
 		if should_print {
@@ -0,0 +1,40 @@
+use std::sync::{Mutex, MutexGuard};
+
+// Cada fatia ocupa a propria linha de cache (128 bytes cobre o prefetch de pares de linhas),
+// para que travas de fatias diferentes nao disputem a mesma linha.
+#[repr(align(128))]
+struct PaddedShard(Mutex<usize>);
+
+// Contador listrado: S fatias travadas de forma independente, escolhidas por thread_id % S.
+// Threads em fatias diferentes nunca se esperam; com S = 1 volta a ser o Mutex unico.
+pub struct ShardedCounter {
+	shards: Vec<PaddedShard>,
+}
+
+impl ShardedCounter {
+	pub fn new(shard_count: usize) -> Self {
+		assert!(shard_count > 0, "Use ao menos uma fatia");
+		Self {
+			shards: (0..shard_count).map(|_| PaddedShard(Mutex::new(0))).collect(),
+		}
+	}
+
+	pub fn shard_count(&self) -> usize {
+		self.shards.len()
+	}
+
+	pub fn add(&self, thread_id: usize, delta: usize) {
+		let shard = &self.shards[thread_id % self.shards.len()];
+		*shard.0.lock().expect("Mutex poisoned") += delta;
+	}
+
+	// Soma consistente: segura todas as fatias (sempre na mesma ordem) antes de ler qualquer uma.
+	pub fn sum(&self) -> usize {
+		let guards: Vec<MutexGuard<'_, usize>> = self
+			.shards
+			.iter()
+			.map(|shard| shard.0.lock().expect("Mutex poisoned"))
+			.collect();
+		guards.iter().map(|guard| **guard).sum()
+	}
+}
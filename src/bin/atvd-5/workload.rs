@@ -0,0 +1,79 @@
+use std::hint;
+use std::time::Instant;
+
+const CALIBRATION_SPINS: u64 = 20_000_000;
+
+// Trabalho por iteracao de cada thread: `critical` roda dentro da secao critica e `outside` fora
+// dela, ambos como lacos ocupados calibrados em nanossegundos. Sem trabalho (0, 0) as variantes
+// voltam ao `+= 1` puro, que exagera o peso da trava.
+#[derive(Clone, Copy)]
+pub struct Workload {
+	pub iterations: usize,
+	pub critical_ns: u64,
+	pub outside_ns: u64,
+	critical_spins: u64,
+	outside_spins: u64,
+}
+
+impl Workload {
+	pub fn plain(iterations: usize) -> Self {
+		Self {
+			iterations,
+			critical_ns: 0,
+			outside_ns: 0,
+			critical_spins: 0,
+			outside_spins: 0,
+		}
+	}
+
+	pub fn with_work(iterations: usize, critical_ns: u64, outside_ns: u64, calibration: &SpinCalibration) -> Self {
+		Self {
+			iterations,
+			critical_ns,
+			outside_ns,
+			critical_spins: calibration.spins_for(critical_ns),
+			outside_spins: calibration.spins_for(outside_ns),
+		}
+	}
+
+	pub fn critical(&self) {
+		busy_work(self.critical_spins);
+	}
+
+	pub fn outside(&self) {
+		busy_work(self.outside_spins);
+	}
+}
+
+// Voltas do laco ocupado por nanossegundo, medidas uma vez nesta maquina.
+pub struct SpinCalibration {
+	spins_per_ns: f64,
+}
+
+impl SpinCalibration {
+	pub fn measure() -> Self {
+		// Uma rodada de aquecimento antes da medida, como nas demais medicoes.
+		busy_work(CALIBRATION_SPINS / 10);
+		let start = Instant::now();
+		busy_work(CALIBRATION_SPINS);
+		let elapsed_ns = start.elapsed().as_nanos().max(1) as f64;
+		Self {
+			spins_per_ns: CALIBRATION_SPINS as f64 / elapsed_ns,
+		}
+	}
+
+	pub fn spins_for(&self, nanos: u64) -> u64 {
+		(nanos as f64 * self.spins_per_ns).round() as u64
+	}
+
+	pub fn spins_per_ns(&self) -> f64 {
+		self.spins_per_ns
+	}
+}
+
+fn busy_work(spins: u64) {
+	for spin in 0..spins {
+		// black_box impede que o compilador elimine o laco vazio.
+		hint::black_box(spin);
+	}
+}
@@ -0,0 +1,138 @@
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+// Operacoes do catalogo; compare_exchange conta uma chamada por operacao, com ou sem sucesso,
+// usando como esperado o ultimo valor visto (sem load extra entre as tentativas).
+#[derive(Clone, Copy)]
+pub enum AtomicOp {
+	FetchAdd,
+	FetchOr,
+	FetchMax,
+	Swap,
+	CompareExchange,
+}
+
+impl AtomicOp {
+	pub const ALL: [AtomicOp; 5] = [
+		AtomicOp::FetchAdd,
+		AtomicOp::FetchOr,
+		AtomicOp::FetchMax,
+		AtomicOp::Swap,
+		AtomicOp::CompareExchange,
+	];
+
+	pub fn label(self) -> &'static str {
+		match self {
+			AtomicOp::FetchAdd => "fetch_add",
+			AtomicOp::FetchOr => "fetch_or",
+			AtomicOp::FetchMax => "fetch_max",
+			AtomicOp::Swap => "swap",
+			AtomicOp::CompareExchange => "compare_exchange",
+		}
+	}
+}
+
+// Compartilhado: todas as threads operam na mesma posicao. Privado: cada thread tem a sua,
+// em linha de cache propria, entao sobra apenas o custo da instrucao em si.
+#[derive(Clone, Copy)]
+pub enum Location {
+	Shared,
+	Private,
+}
+
+impl Location {
+	pub fn label(self) -> &'static str {
+		match self {
+			Location::Shared => "compartilhado",
+			Location::Private => "privado",
+		}
+	}
+}
+
+pub trait AtomicWord: Send + Sync + 'static {
+	const LABEL: &'static str;
+
+	fn zero() -> Self;
+
+	// Executa uma operacao; `expected` guarda o ultimo valor visto pelo compare_exchange da thread.
+	fn apply(&self, op: AtomicOp, thread_id: usize, iter: usize, expected: &mut u64);
+}
+
+macro_rules! impl_atomic_word {
+	($atomic:ty, $int:ty, $label:expr) => {
+		impl AtomicWord for $atomic {
+			const LABEL: &'static str = $label;
+
+			fn zero() -> Self {
+				<$atomic>::new(0)
+			}
+
+			fn apply(&self, op: AtomicOp, thread_id: usize, iter: usize, expected: &mut u64) {
+				match op {
+					AtomicOp::FetchAdd => {
+						self.fetch_add(1, Ordering::Relaxed);
+					}
+					AtomicOp::FetchOr => {
+						self.fetch_or(1 << (thread_id % <$int>::BITS as usize), Ordering::Relaxed);
+					}
+					AtomicOp::FetchMax => {
+						self.fetch_max(iter as $int, Ordering::Relaxed);
+					}
+					AtomicOp::Swap => {
+						self.swap(iter as $int, Ordering::Relaxed);
+					}
+					AtomicOp::CompareExchange => {
+						let current = *expected as $int;
+						*expected = match self.compare_exchange(
+							current,
+							current.wrapping_add(1),
+							Ordering::AcqRel,
+							Ordering::Relaxed,
+						) {
+							Ok(_) => current.wrapping_add(1) as u64,
+							Err(observed) => observed as u64,
+						};
+					}
+				}
+			}
+		}
+	};
+}
+
+impl_atomic_word!(AtomicU8, u8, "AtomicU8");
+impl_atomic_word!(AtomicU32, u32, "AtomicU32");
+impl_atomic_word!(AtomicU64, u64, "AtomicU64");
+impl_atomic_word!(AtomicUsize, usize, "AtomicUsize");
+
+#[repr(align(128))]
+struct PaddedWord<A>(A);
+
+// Cada thread aplica `op` `iterations` vezes na sua posicao, cedendo o processador a cada 1024 operacoes;
+// sem prints nem registro de nucleo, so a operacao atomica entra no tempo medido.
+pub fn atomic_op_counter<A: AtomicWord>(thread_count: usize, iterations: usize, op: AtomicOp, location: Location) {
+	let slots = match location {
+		Location::Shared => 1,
+		Location::Private => thread_count,
+	};
+	let words: Arc<Vec<PaddedWord<A>>> = Arc::new((0..slots).map(|_| PaddedWord(A::zero())).collect());
+	let mut handles = Vec::with_capacity(thread_count);
+
+	for thread_id in 0..thread_count {
+		let words_clone = Arc::clone(&words);
+		handles.push(thread::spawn(move || {
+			let word = &words_clone[thread_id % slots].0;
+			let mut expected = 0u64;
+			for iter in 0..iterations {
+				word.apply(op, thread_id, iter, &mut expected);
+				if iter % 1024 == 0 {
+					thread::yield_now();
+				}
+			}
+		}));
+	}
+
+	for handle in handles {
+		handle.join().expect("Thread panicked during execution");
+	}
+}
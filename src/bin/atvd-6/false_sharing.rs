@@ -0,0 +1,53 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+// 128 bytes: uma linha de cache de 64 bytes mais a vizinha que o prefetcher de pares (Intel) traz junto.
+#[repr(align(128))]
+struct PaddedCounter(AtomicUsize);
+
+// Contadores por thread lado a lado: oito AtomicUsize dividem a mesma linha de 64 bytes, entao cada
+// incremento invalida a linha nos outros nucleos mesmo sem nenhum dado realmente compartilhado.
+pub fn packed_counters(thread_count: usize, iterations: usize) -> usize {
+	let counters: Arc<Vec<AtomicUsize>> = Arc::new((0..thread_count).map(|_| AtomicUsize::new(0)).collect());
+	run_private_counters(thread_count, iterations, Arc::clone(&counters), |counters, thread_id| &counters[thread_id]);
+	counters.iter().map(|counter| counter.load(Ordering::Relaxed)).sum()
+}
+
+// Mesmos contadores, cada um na propria linha: nenhum nucleo invalida a linha de outro.
+pub fn padded_counters(thread_count: usize, iterations: usize) -> usize {
+	let counters: Arc<Vec<PaddedCounter>> =
+		Arc::new((0..thread_count).map(|_| PaddedCounter(AtomicUsize::new(0))).collect());
+	run_private_counters(thread_count, iterations, Arc::clone(&counters), |counters, thread_id| {
+		&counters[thread_id].0
+	});
+	counters.iter().map(|counter| counter.0.load(Ordering::Relaxed)).sum()
+}
+
+fn run_private_counters<C>(
+	thread_count: usize,
+	iterations: usize,
+	counters: Arc<C>,
+	slot: fn(&C, usize) -> &AtomicUsize,
+) where
+	C: Send + Sync + 'static,
+{
+	let mut handles = Vec::with_capacity(thread_count);
+
+	for thread_id in 0..thread_count {
+		let counters_clone = Arc::clone(&counters);
+		handles.push(thread::spawn(move || {
+			let counter = slot(&counters_clone, thread_id);
+			for iter in 0..iterations {
+				counter.fetch_add(1, Ordering::Relaxed);
+				if iter % 1024 == 0 {
+					thread::yield_now();
+				}
+			}
+		}));
+	}
+
+	for handle in handles {
+		handle.join().expect("Thread panicked during execution");
+	}
+}
@@ -0,0 +1,279 @@
+use std::collections::BTreeMap;
+use std::hint;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+const SPIN_LIMIT: u32 = 64;
+
+// Ordenacao aplicada ao teste inteiro: escritas usam a versao de store e leituras a de load.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LitmusOrdering {
+	Relaxed,
+	AcquireRelease,
+	SeqCst,
+}
+
+impl LitmusOrdering {
+	pub const ALL: [LitmusOrdering; 3] = [
+		LitmusOrdering::Relaxed,
+		LitmusOrdering::AcquireRelease,
+		LitmusOrdering::SeqCst,
+	];
+
+	pub fn parse(text: &str) -> Option<Self> {
+		match text {
+			"relaxed" => Some(LitmusOrdering::Relaxed),
+			"acqrel" => Some(LitmusOrdering::AcquireRelease),
+			"seqcst" => Some(LitmusOrdering::SeqCst),
+			_ => None,
+		}
+	}
+
+	pub fn label(self) -> &'static str {
+		match self {
+			LitmusOrdering::Relaxed => "Relaxed",
+			LitmusOrdering::AcquireRelease => "Release/Acquire",
+			LitmusOrdering::SeqCst => "SeqCst",
+		}
+	}
+
+	fn store(self) -> Ordering {
+		match self {
+			LitmusOrdering::Relaxed => Ordering::Relaxed,
+			LitmusOrdering::AcquireRelease => Ordering::Release,
+			LitmusOrdering::SeqCst => Ordering::SeqCst,
+		}
+	}
+
+	fn load(self) -> Ordering {
+		match self {
+			LitmusOrdering::Relaxed => Ordering::Relaxed,
+			LitmusOrdering::AcquireRelease => Ordering::Acquire,
+			LitmusOrdering::SeqCst => Ordering::SeqCst,
+		}
+	}
+}
+
+// Testes classicos sobre duas posicoes x e y, ambas iniciando em 0.
+#[derive(Clone, Copy)]
+pub enum LitmusTest {
+	// T0: x = 1; y = 1      T1: r1 = y; r2 = x       fraco: r1 = 1, r2 = 0
+	MessagePassing,
+	// T0: x = 1; r1 = y     T1: y = 1; r2 = x        fraco: r1 = 0, r2 = 0
+	StoreBuffering,
+	// T0: r1 = x; y = 1     T1: r2 = y; x = 1        fraco: r1 = 1, r2 = 1
+	LoadBuffering,
+	// T0: x = 1  T1: y = 1  T2: r1 = x; r2 = y  T3: r3 = y; r4 = x   fraco: 1, 0, 1, 0
+	Iriw,
+	// T0: x = 1; y = 2      T1: y = 1; x = 2         fraco: final x = 1, y = 1
+	TwoPlusTwoW,
+}
+
+impl LitmusTest {
+	pub const ALL: [LitmusTest; 5] = [
+		LitmusTest::MessagePassing,
+		LitmusTest::StoreBuffering,
+		LitmusTest::LoadBuffering,
+		LitmusTest::Iriw,
+		LitmusTest::TwoPlusTwoW,
+	];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			LitmusTest::MessagePassing => "MP (passagem de mensagem)",
+			LitmusTest::StoreBuffering => "SB (buffer de escrita)",
+			LitmusTest::LoadBuffering => "LB (buffer de leitura)",
+			LitmusTest::Iriw => "IRIW (leituras independentes)",
+			LitmusTest::TwoPlusTwoW => "2+2W (escritas cruzadas)",
+		}
+	}
+
+	pub fn outcome_labels(self) -> &'static str {
+		match self {
+			LitmusTest::MessagePassing | LitmusTest::StoreBuffering | LitmusTest::LoadBuffering => "r1 r2",
+			LitmusTest::Iriw => "r1 r2 r3 r4",
+			LitmusTest::TwoPlusTwoW => "x y (final)",
+		}
+	}
+
+	pub fn weak_outcome(self) -> Vec<u32> {
+		match self {
+			LitmusTest::MessagePassing => vec![1, 0],
+			LitmusTest::StoreBuffering => vec![0, 0],
+			LitmusTest::LoadBuffering => vec![1, 1],
+			LitmusTest::Iriw => vec![1, 0, 1, 0],
+			LitmusTest::TwoPlusTwoW => vec![1, 1],
+		}
+	}
+
+	// Se o resultado fraco e proibido pelo modelo de memoria do Rust (C++20) nesta ordenacao.
+	pub fn is_forbidden(self, ordering: LitmusOrdering) -> bool {
+		match self {
+			// Release/acquire ja cria happens-before entre a escrita de y e a leitura de x.
+			LitmusTest::MessagePassing | LitmusTest::LoadBuffering => ordering != LitmusOrdering::Relaxed,
+			// Exigem uma ordem total unica entre escritas e leituras de posicoes diferentes.
+			LitmusTest::StoreBuffering | LitmusTest::Iriw | LitmusTest::TwoPlusTwoW => {
+				ordering == LitmusOrdering::SeqCst
+			}
+		}
+	}
+
+	fn threads(self) -> usize {
+		match self {
+			LitmusTest::Iriw => 4,
+			_ => 2,
+		}
+	}
+
+	// Corpo de uma thread; devolve ate dois registradores lidos (0 quando a thread so escreve).
+	fn run_thread(self, thread: usize, x: &AtomicU32, y: &AtomicU32, ordering: LitmusOrdering) -> [u32; 2] {
+		let (store, load) = (ordering.store(), ordering.load());
+		match (self, thread) {
+			(LitmusTest::MessagePassing, 0) => {
+				x.store(1, store);
+				y.store(1, store);
+				[0, 0]
+			}
+			(LitmusTest::MessagePassing, _) => [y.load(load), x.load(load)],
+			(LitmusTest::StoreBuffering, 0) => {
+				x.store(1, store);
+				[y.load(load), 0]
+			}
+			(LitmusTest::StoreBuffering, _) => {
+				y.store(1, store);
+				[x.load(load), 0]
+			}
+			(LitmusTest::LoadBuffering, 0) => {
+				let r1 = x.load(load);
+				y.store(1, store);
+				[r1, 0]
+			}
+			(LitmusTest::LoadBuffering, _) => {
+				let r2 = y.load(load);
+				x.store(1, store);
+				[r2, 0]
+			}
+			(LitmusTest::Iriw, 0) => {
+				x.store(1, store);
+				[0, 0]
+			}
+			(LitmusTest::Iriw, 1) => {
+				y.store(1, store);
+				[0, 0]
+			}
+			(LitmusTest::Iriw, 2) => [x.load(load), y.load(load)],
+			(LitmusTest::Iriw, _) => [y.load(load), x.load(load)],
+			(LitmusTest::TwoPlusTwoW, 0) => {
+				x.store(1, store);
+				y.store(2, store);
+				[0, 0]
+			}
+			(LitmusTest::TwoPlusTwoW, _) => {
+				y.store(1, store);
+				x.store(2, store);
+				[0, 0]
+			}
+		}
+	}
+
+	fn outcome(self, registers: &[[u32; 2]], x: u32, y: u32) -> Vec<u32> {
+		match self {
+			LitmusTest::MessagePassing => registers[1].to_vec(),
+			LitmusTest::StoreBuffering | LitmusTest::LoadBuffering => vec![registers[0][0], registers[1][0]],
+			LitmusTest::Iriw => vec![registers[2][0], registers[2][1], registers[3][0], registers[3][1]],
+			LitmusTest::TwoPlusTwoW => vec![x, y],
+		}
+	}
+}
+
+pub struct LitmusResult {
+	pub outcomes: BTreeMap<Vec<u32>, usize>,
+}
+
+impl LitmusResult {
+	pub fn weak_count(&self, test: LitmusTest) -> usize {
+		self.outcomes.get(&test.weak_outcome()).copied().unwrap_or(0)
+	}
+}
+
+// Barreira de espera ativa reutilizavel (sentido alternado): alinha as threads no inicio de
+// cada iteracao sem passar pelo kernel, para que os corpos do teste realmente se sobreponham.
+struct SpinBarrier {
+	parties: usize,
+	arrived: AtomicUsize,
+	sense: AtomicBool,
+}
+
+impl SpinBarrier {
+	fn new(parties: usize) -> Self {
+		Self {
+			parties,
+			arrived: AtomicUsize::new(0),
+			sense: AtomicBool::new(false),
+		}
+	}
+
+	fn wait(&self, local_sense: &mut bool) {
+		*local_sense = !*local_sense;
+		if self.arrived.fetch_add(1, Ordering::AcqRel) + 1 == self.parties {
+			self.arrived.store(0, Ordering::Relaxed);
+			self.sense.store(*local_sense, Ordering::Release);
+			return;
+		}
+		let mut spins = 0;
+		while self.sense.load(Ordering::Acquire) != *local_sense {
+			if spins < SPIN_LIMIT {
+				spins += 1;
+				hint::spin_loop();
+			} else {
+				thread::yield_now();
+			}
+		}
+	}
+}
+
+// Cada iteracao usa um par x/y proprio, entao nao ha limpeza entre iteracoes: a barreira so
+// alinha o inicio. Os registradores ficam em vetores locais e sao apurados no final.
+pub fn run_litmus(test: LitmusTest, ordering: LitmusOrdering, iterations: usize) -> LitmusResult {
+	let threads = test.threads();
+	let xs: Arc<Vec<AtomicU32>> = Arc::new((0..iterations).map(|_| AtomicU32::new(0)).collect());
+	let ys: Arc<Vec<AtomicU32>> = Arc::new((0..iterations).map(|_| AtomicU32::new(0)).collect());
+	let barrier = Arc::new(SpinBarrier::new(threads));
+
+	let handles: Vec<_> = (0..threads)
+		.map(|thread| {
+			let (xs, ys, barrier) = (Arc::clone(&xs), Arc::clone(&ys), Arc::clone(&barrier));
+			thread::spawn(move || {
+				let mut local_sense = false;
+				let mut registers = Vec::with_capacity(iterations);
+				for iter in 0..iterations {
+					barrier.wait(&mut local_sense);
+					registers.push(test.run_thread(thread, &xs[iter], &ys[iter], ordering));
+				}
+				registers
+			})
+		})
+		.collect();
+
+	let registers: Vec<Vec<[u32; 2]>> = handles
+		.into_iter()
+		.map(|handle| handle.join().expect("Thread panicked during execution"))
+		.collect();
+
+	let mut outcomes = BTreeMap::new();
+	let mut per_iteration = vec![[0u32; 2]; threads];
+	for iter in 0..iterations {
+		for (thread, thread_registers) in registers.iter().enumerate() {
+			per_iteration[thread] = thread_registers[iter];
+		}
+		let outcome = test.outcome(
+			&per_iteration,
+			xs[iter].load(Ordering::Relaxed),
+			ys[iter].load(Ordering::Relaxed),
+		);
+		*outcomes.entry(outcome).or_insert(0) += 1;
+	}
+
+	LitmusResult { outcomes }
+}
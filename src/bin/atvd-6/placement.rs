@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+// Amostras de `sched_getcpu` feitas por uma thread no inicio, no meio e no fim do trabalho.
+// Fora do Linux a chamada nao existe e as amostras ficam como `None`.
+#[derive(Clone, Copy, Debug)]
+pub struct ThreadPlacement {
+	pub thread_id: usize,
+	pub start_cpu: Option<usize>,
+	pub mid_cpu: Option<usize>,
+	pub end_cpu: Option<usize>,
+}
+
+impl ThreadPlacement {
+	pub fn start(thread_id: usize) -> Self {
+		Self {
+			thread_id,
+			start_cpu: current_cpu(),
+			mid_cpu: None,
+			end_cpu: None,
+		}
+	}
+
+	pub fn mark_midway(&mut self) {
+		self.mid_cpu = current_cpu();
+	}
+
+	pub fn finish(mut self) -> Self {
+		self.end_cpu = current_cpu();
+		self
+	}
+
+	fn samples(&self) -> impl Iterator<Item = usize> + '_ {
+		[self.start_cpu, self.mid_cpu, self.end_cpu].into_iter().flatten()
+	}
+
+	// Conta trocas de nucleo entre amostras consecutivas (no maximo 2 por thread).
+	pub fn migrations(&self) -> usize {
+		let samples: Vec<usize> = self.samples().collect();
+		samples.windows(2).filter(|pair| pair[0] != pair[1]).count()
+	}
+}
+
+#[cfg(target_os = "linux")]
+pub fn current_cpu() -> Option<usize> {
+	unsafe extern "C" {
+		fn sched_getcpu() -> i32;
+	}
+
+	// SAFETY: sched_getcpu nao recebe argumentos e apenas consulta o nucleo atual da thread.
+	let cpu = unsafe { sched_getcpu() };
+	usize::try_from(cpu).ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn current_cpu() -> Option<usize> {
+	None
+}
+
+pub fn report_placement(placements: &[ThreadPlacement]) {
+	if placements.iter().all(|placement| placement.samples().next().is_none()) {
+		println!("  sched_getcpu indisponivel nesta plataforma; mapa de nucleos omitido.");
+		return;
+	}
+
+	let mut threads_per_cpu: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+	for placement in placements {
+		let mut seen: Vec<usize> = placement.samples().collect();
+		seen.sort_unstable();
+		seen.dedup();
+		for cpu in seen {
+			threads_per_cpu.entry(cpu).or_default().push(placement.thread_id);
+		}
+	}
+
+	println!("  Thread | inicio | meio | fim | migracoes");
+	for placement in placements {
+		println!(
+			"  {:>6} | {:>6} | {:>4} | {:>3} | {:>9}",
+			placement.thread_id,
+			format_cpu(placement.start_cpu),
+			format_cpu(placement.mid_cpu),
+			format_cpu(placement.end_cpu),
+			placement.migrations()
+		);
+	}
+
+	println!("  Threads por nucleo:");
+	for (cpu, threads) in &threads_per_cpu {
+		println!("    CPU {:>3}: {} thread(s) {:?}", cpu, threads.len(), threads);
+	}
+
+	let migrations: usize = placements.iter().map(ThreadPlacement::migrations).sum();
+	let migrated_threads = placements.iter().filter(|placement| placement.migrations() > 0).count();
+	println!(
+		"  Nucleos usados: {} | migracoes observadas: {} ({} de {} threads migraram)",
+		threads_per_cpu.len(),
+		migrations,
+		migrated_threads,
+		placements.len()
+	);
+}
+
+fn format_cpu(cpu: Option<usize>) -> String {
+	cpu.map_or_else(|| String::from("-"), |cpu| cpu.to_string())
+}
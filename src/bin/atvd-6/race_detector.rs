@@ -0,0 +1,251 @@
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+
+// Detector de corridas por happens-before (estilo DJIT+/FastTrack simplificado).
+// Cada thread carrega um relogio vetorial; travas, atomicos com acquire/release, spawn e join
+// propagam relogios, e acessos a um `TrackedCell` nao ordenados por eles sao reportados.
+const MAX_SAMPLES: usize = 5;
+
+#[derive(Clone, Debug)]
+struct VectorClock(Vec<u64>);
+
+impl VectorClock {
+	fn new(threads: usize) -> Self {
+		Self(vec![0; threads])
+	}
+
+	fn join(&mut self, other: &VectorClock) {
+		for (mine, theirs) in self.0.iter_mut().zip(&other.0) {
+			*mine = (*mine).max(*theirs);
+		}
+	}
+}
+
+// Contexto de uma thread instrumentada. Os ids vao de 0 a threads-1; o id `threads` e a main.
+pub struct ThreadCtx {
+	id: usize,
+	clock: VectorClock,
+}
+
+impl ThreadCtx {
+	pub fn main(worker_threads: usize) -> Self {
+		let mut clock = VectorClock::new(worker_threads + 1);
+		clock.0[worker_threads] = 1;
+		Self {
+			id: worker_threads,
+			clock,
+		}
+	}
+
+	// spawn: tudo que o pai fez antes acontece-antes de tudo que o filho fizer.
+	pub fn fork(&mut self, child_id: usize) -> ThreadCtx {
+		let mut clock = self.clock.clone();
+		clock.0[child_id] += 1;
+		self.tick();
+		ThreadCtx { id: child_id, clock }
+	}
+
+	// join: tudo que o filho fez acontece-antes do que o pai fizer depois.
+	pub fn join(&mut self, child: ThreadCtx) {
+		self.clock.join(&child.clock);
+		self.tick();
+	}
+
+	fn epoch(&self) -> u64 {
+		self.clock.0[self.id]
+	}
+
+	fn knows(&self, access: &Access) -> bool {
+		access.thread == self.id || access.epoch <= self.clock.0[access.thread]
+	}
+
+	fn tick(&mut self) {
+		self.clock.0[self.id] += 1;
+	}
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Access {
+	thread: usize,
+	epoch: u64,
+	iteration: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ConflictKind {
+	WriteWrite,
+	WriteRead,
+	ReadWrite,
+}
+
+impl ConflictKind {
+	pub fn label(self) -> &'static str {
+		match self {
+			ConflictKind::WriteWrite => "escrita-escrita",
+			ConflictKind::WriteRead => "escrita-leitura",
+			ConflictKind::ReadWrite => "leitura-escrita",
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RaceReport {
+	pub kind: ConflictKind,
+	pub first_thread: usize,
+	pub first_iteration: usize,
+	pub second_thread: usize,
+	pub second_iteration: usize,
+}
+
+// Coletor compartilhado: guarda o total e as primeiras ocorrencias para exibir.
+#[derive(Default)]
+pub struct RaceLog {
+	state: Mutex<RaceLogState>,
+}
+
+#[derive(Default)]
+struct RaceLogState {
+	total: usize,
+	samples: Vec<RaceReport>,
+}
+
+impl RaceLog {
+	fn record(&self, kind: ConflictKind, earlier: &Access, later: &Access) {
+		let mut state = self.state.lock().expect("Mutex poisoned");
+		state.total += 1;
+		if state.samples.len() < MAX_SAMPLES {
+			state.samples.push(RaceReport {
+				kind,
+				first_thread: earlier.thread,
+				first_iteration: earlier.iteration,
+				second_thread: later.thread,
+				second_iteration: later.iteration,
+			});
+		}
+	}
+
+	pub fn total(&self) -> usize {
+		self.state.lock().expect("Mutex poisoned").total
+	}
+
+	pub fn samples(&self) -> Vec<RaceReport> {
+		self.state.lock().expect("Mutex poisoned").samples.clone()
+	}
+}
+
+// Variavel comum (nao sincronizada) cujos acessos sao verificados contra o historico.
+pub struct TrackedCell {
+	state: Mutex<CellState>,
+	log: Arc<RaceLog>,
+}
+
+struct CellState {
+	value: usize,
+	last_write: Option<Access>,
+	reads: Vec<Option<Access>>,
+}
+
+impl TrackedCell {
+	pub fn new(value: usize, threads: usize, log: Arc<RaceLog>) -> Self {
+		Self {
+			state: Mutex::new(CellState {
+				value,
+				last_write: None,
+				reads: vec![None; threads + 1],
+			}),
+			log,
+		}
+	}
+
+	pub fn read(&self, ctx: &ThreadCtx, iteration: usize) -> usize {
+		let access = Access {
+			thread: ctx.id,
+			epoch: ctx.epoch(),
+			iteration,
+		};
+		let mut state = self.state.lock().expect("Mutex poisoned");
+		if let Some(write) = state.last_write.filter(|write| !ctx.knows(write)) {
+			self.log.record(ConflictKind::WriteRead, &write, &access);
+		}
+		state.reads[ctx.id] = Some(access);
+		state.value
+	}
+
+	pub fn write(&self, ctx: &ThreadCtx, iteration: usize, value: usize) {
+		let access = Access {
+			thread: ctx.id,
+			epoch: ctx.epoch(),
+			iteration,
+		};
+		let mut state = self.state.lock().expect("Mutex poisoned");
+		if let Some(write) = state.last_write.filter(|write| !ctx.knows(write)) {
+			self.log.record(ConflictKind::WriteWrite, &write, &access);
+		}
+		for read in state.reads.iter().flatten().filter(|read| !ctx.knows(read)) {
+			self.log.record(ConflictKind::ReadWrite, read, &access);
+		}
+		// Leituras anteriores ja foram verificadas contra esta escrita.
+		state.reads.iter_mut().for_each(|read| *read = None);
+		state.last_write = Some(access);
+		state.value = value;
+	}
+}
+
+// Mutex instrumentado: liberar publica o relogio da thread, adquirir o incorpora.
+pub struct TrackedMutex<T> {
+	inner: Mutex<(T, VectorClock)>,
+}
+
+impl<T> TrackedMutex<T> {
+	pub fn new(value: T, threads: usize) -> Self {
+		Self {
+			inner: Mutex::new((value, VectorClock::new(threads + 1))),
+		}
+	}
+
+	pub fn with_lock<R>(&self, ctx: &mut ThreadCtx, critical: impl FnOnce(&mut ThreadCtx, &mut T) -> R) -> R {
+		let mut guard = self.inner.lock().expect("Mutex poisoned");
+		ctx.clock.join(&guard.1);
+		let result = critical(ctx, &mut guard.0);
+		guard.1 = ctx.clock.clone();
+		ctx.tick();
+		result
+	}
+}
+
+// Atomico instrumentado: acessos atomicos nunca correm entre si; so criam happens-before
+// quando a ordenacao pede (Release publica, Acquire incorpora).
+pub struct TrackedAtomic {
+	inner: Mutex<(usize, VectorClock)>,
+}
+
+impl TrackedAtomic {
+	pub fn new(value: usize, threads: usize) -> Self {
+		Self {
+			inner: Mutex::new((value, VectorClock::new(threads + 1))),
+		}
+	}
+
+	pub fn fetch_add(&self, ctx: &mut ThreadCtx, delta: usize, ordering: Ordering) -> usize {
+		let mut guard = self.inner.lock().expect("Mutex poisoned");
+		if matches!(ordering, Ordering::Acquire | Ordering::AcqRel | Ordering::SeqCst) {
+			ctx.clock.join(&guard.1);
+		}
+		let previous = guard.0;
+		guard.0 += delta;
+		if matches!(ordering, Ordering::Release | Ordering::AcqRel | Ordering::SeqCst) {
+			// RMWs estendem a sequencia de release: o relogio publicado acumula.
+			guard.1.join(&ctx.clock);
+			ctx.tick();
+		}
+		previous
+	}
+
+	pub fn load(&self, ctx: &mut ThreadCtx, ordering: Ordering) -> usize {
+		let guard = self.inner.lock().expect("Mutex poisoned");
+		if matches!(ordering, Ordering::Acquire | Ordering::SeqCst) {
+			ctx.clock.join(&guard.1);
+		}
+		guard.0
+	}
+}
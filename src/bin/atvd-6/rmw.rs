@@ -0,0 +1,129 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+// Formas de incrementar o contador atomico. fetch_add e uma unica instrucao RMW (lock xadd no x86,
+// ldadd/LL-SC no ARM) e nunca repete; os lacos de CAS repetem quando outra thread escreveu entre a
+// leitura e a troca, e o CAS fraco pode ainda falhar espuriamente em arquiteturas LL/SC.
+#[derive(Clone, Copy)]
+pub enum RmwVariant {
+	FetchAddRelaxed,
+	FetchAddAcqRel,
+	FetchAddSeqCst,
+	CompareExchange,
+	CompareExchangeWeak,
+	FetchUpdate,
+}
+
+impl RmwVariant {
+	pub const ALL: [RmwVariant; 6] = [
+		RmwVariant::FetchAddRelaxed,
+		RmwVariant::FetchAddAcqRel,
+		RmwVariant::FetchAddSeqCst,
+		RmwVariant::CompareExchange,
+		RmwVariant::CompareExchangeWeak,
+		RmwVariant::FetchUpdate,
+	];
+
+	pub fn label(self) -> &'static str {
+		match self {
+			RmwVariant::FetchAddRelaxed => "fetch_add Relaxed",
+			RmwVariant::FetchAddAcqRel => "fetch_add AcqRel",
+			RmwVariant::FetchAddSeqCst => "fetch_add SeqCst",
+			RmwVariant::CompareExchange => "compare_exchange",
+			RmwVariant::CompareExchangeWeak => "compare_exchange_weak",
+			RmwVariant::FetchUpdate => "fetch_update",
+		}
+	}
+
+	// Incrementa uma vez e devolve quantas tentativas falharam antes do sucesso.
+	fn increment(self, counter: &AtomicUsize) -> usize {
+		match self {
+			RmwVariant::FetchAddRelaxed => {
+				counter.fetch_add(1, Ordering::Relaxed);
+				0
+			}
+			RmwVariant::FetchAddAcqRel => {
+				counter.fetch_add(1, Ordering::AcqRel);
+				0
+			}
+			RmwVariant::FetchAddSeqCst => {
+				counter.fetch_add(1, Ordering::SeqCst);
+				0
+			}
+			RmwVariant::CompareExchange => {
+				let mut retries = 0;
+				let mut current = counter.load(Ordering::Relaxed);
+				while let Err(observed) =
+					counter.compare_exchange(current, current + 1, Ordering::AcqRel, Ordering::Relaxed)
+				{
+					current = observed;
+					retries += 1;
+				}
+				retries
+			}
+			RmwVariant::CompareExchangeWeak => {
+				let mut retries = 0;
+				let mut current = counter.load(Ordering::Relaxed);
+				while let Err(observed) =
+					counter.compare_exchange_weak(current, current + 1, Ordering::AcqRel, Ordering::Relaxed)
+				{
+					current = observed;
+					retries += 1;
+				}
+				retries
+			}
+			RmwVariant::FetchUpdate => {
+				// fetch_update e um laco de compare_exchange_weak; cada chamada extra do fechamento e uma repeticao.
+				let mut calls = 0usize;
+				counter
+					.fetch_update(Ordering::AcqRel, Ordering::Relaxed, |value| {
+						calls += 1;
+						Some(value + 1)
+					})
+					.expect("O fechamento sempre devolve Some");
+				calls - 1
+			}
+		}
+	}
+}
+
+pub struct RmwRun {
+	pub total: usize,
+	pub retries_per_thread: Vec<usize>,
+}
+
+impl RmwRun {
+	pub fn retries(&self) -> usize {
+		self.retries_per_thread.iter().sum()
+	}
+}
+
+pub fn rmw_counter(thread_count: usize, iterations: usize, variant: RmwVariant) -> RmwRun {
+	let counter = Arc::new(AtomicUsize::new(0));
+	let mut handles = Vec::with_capacity(thread_count);
+
+	for _ in 0..thread_count {
+		let counter_clone = Arc::clone(&counter);
+		handles.push(thread::spawn(move || {
+			let mut retries = 0usize;
+			for iter in 0..iterations {
+				retries += variant.increment(&counter_clone);
+				if iter % 1024 == 0 {
+					thread::yield_now();
+				}
+			}
+			retries
+		}));
+	}
+
+	let retries_per_thread = handles
+		.into_iter()
+		.map(|handle| handle.join().expect("Thread panicked during execution"))
+		.collect();
+
+	RmwRun {
+		total: counter.load(Ordering::Relaxed),
+		retries_per_thread,
+	}
+}
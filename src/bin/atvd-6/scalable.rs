@@ -0,0 +1,72 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::OnceLock;
+
+// Celula em linha de cache propria, como as fatias do LongAdder do Java.
+#[repr(align(128))]
+struct Cell(AtomicI64);
+
+// Contador escalavel no estilo LongAdder: enquanto nao ha disputa, todos somam na base com CAS;
+// na primeira falha do CAS as celulas sao alocadas e cada thread passa a somar na sua
+// (thread_hint % celulas), sem dividir linha de cache com as demais.
+// `sum()` le a base e as celulas uma a uma, sem trava: com escritas em andamento o resultado
+// pode nao corresponder a nenhum instante real do contador (nao e linearizavel).
+pub struct ScalableCounter {
+	base: AtomicI64,
+	cells: OnceLock<Box<[Cell]>>,
+	cell_count: usize,
+}
+
+impl ScalableCounter {
+	pub fn new(cell_count: usize) -> Self {
+		assert!(cell_count > 0, "Use ao menos uma celula");
+		Self {
+			base: AtomicI64::new(0),
+			cells: OnceLock::new(),
+			cell_count,
+		}
+	}
+
+	// Ja nasce com as celulas alocadas, como se a disputa tivesse acontecido.
+	pub fn inflated(cell_count: usize) -> Self {
+		let counter = Self::new(cell_count);
+		counter.cells_or_init();
+		counter
+	}
+
+	fn cells_or_init(&self) -> &[Cell] {
+		self.cells
+			.get_or_init(|| (0..self.cell_count).map(|_| Cell(AtomicI64::new(0))).collect())
+	}
+
+	pub fn add(&self, thread_hint: usize, delta: i64) {
+		if let Some(cells) = self.cells.get() {
+			cells[thread_hint % cells.len()].0.fetch_add(delta, Ordering::Relaxed);
+			return;
+		}
+
+		let current = self.base.load(Ordering::Relaxed);
+		if self
+			.base
+			.compare_exchange(current, current + delta, Ordering::Relaxed, Ordering::Relaxed)
+			.is_ok()
+		{
+			return;
+		}
+
+		// Primeira disputa na base: a partir daqui cada thread usa a propria celula.
+		let cells = self.cells_or_init();
+		cells[thread_hint % cells.len()].0.fetch_add(delta, Ordering::Relaxed);
+	}
+
+	pub fn sum(&self) -> i64 {
+		let cells_total: i64 = self
+			.cells
+			.get()
+			.map_or(0, |cells| cells.iter().map(|cell| cell.0.load(Ordering::Relaxed)).sum());
+		self.base.load(Ordering::Relaxed) + cells_total
+	}
+
+	pub fn is_inflated(&self) -> bool {
+		self.cells.get().is_some()
+	}
+}
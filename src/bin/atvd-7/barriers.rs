@@ -0,0 +1,296 @@
+use std::hint;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Barrier, Condvar, Mutex};
+use std::thread;
+
+// Voltas de espera ativa antes de ceder a CPU (ou de dormir, na versao que estaciona).
+const SPIN_LIMIT: u32 = 128;
+// Quantos filhos cada no da arvore de combinacao agrega.
+const TREE_FAN_IN: usize = 4;
+
+// Barreira reutilizavel em que cada participante se identifica pelo proprio indice (0..parties),
+// o que permite guardar estado local por thread (sentido, episodio) dentro da propria barreira.
+pub trait PhaseBarrier: Send + Sync {
+	fn wait(&self, thread_id: usize);
+}
+
+#[repr(align(128))]
+struct Padded<T>(T);
+
+fn padded_slots<T>(count: usize, make: impl Fn() -> T) -> Box<[Padded<T>]> {
+	(0..count).map(|_| Padded(make())).collect()
+}
+
+fn spin_until(ready: impl Fn() -> bool) {
+	let mut spins = 0;
+	while !ready() {
+		if spins < SPIN_LIMIT {
+			spins += 1;
+			hint::spin_loop();
+		} else {
+			thread::yield_now();
+		}
+	}
+}
+
+// Rodadas necessarias para combinar n participantes dois a dois: ceil(log2 n).
+fn rounds_for(parties: usize) -> usize {
+	(usize::BITS - parties.saturating_sub(1).leading_zeros()) as usize
+}
+
+// Referencia: std::sync::Barrier (mutex + condvar).
+pub struct StdBarrier(Barrier);
+
+impl StdBarrier {
+	pub fn new(parties: usize) -> Self {
+		Self(Barrier::new(parties))
+	}
+}
+
+impl PhaseBarrier for StdBarrier {
+	fn wait(&self, _thread_id: usize) {
+		self.0.wait();
+	}
+}
+
+#[derive(Clone, Copy)]
+pub enum WaitStrategy {
+	// Gira (cedendo a CPU apos SPIN_LIMIT voltas) ate a liberacao.
+	Spin,
+	// Gira SPIN_LIMIT voltas e depois dorme em uma condvar ate o ultimo a chegar acordar todos.
+	SpinThenPark,
+}
+
+// Barreira centralizada com inversao de sentido: um contador de chegadas e uma flag global cujo
+// valor alterna a cada episodio; cada thread espera a flag igualar o seu sentido local.
+pub struct SenseBarrier {
+	parties: usize,
+	arrived: AtomicUsize,
+	sense: AtomicBool,
+	local_sense: Box<[Padded<AtomicBool>]>,
+	strategy: WaitStrategy,
+	parked: Mutex<()>,
+	released: Condvar,
+}
+
+impl SenseBarrier {
+	pub fn new(parties: usize, strategy: WaitStrategy) -> Self {
+		Self {
+			parties,
+			arrived: AtomicUsize::new(0),
+			sense: AtomicBool::new(false),
+			local_sense: padded_slots(parties, || AtomicBool::new(false)),
+			strategy,
+			parked: Mutex::new(()),
+			released: Condvar::new(),
+		}
+	}
+}
+
+impl PhaseBarrier for SenseBarrier {
+	fn wait(&self, thread_id: usize) {
+		let local_sense = !self.local_sense[thread_id].0.load(Ordering::Relaxed);
+		self.local_sense[thread_id].0.store(local_sense, Ordering::Relaxed);
+
+		if self.arrived.fetch_add(1, Ordering::AcqRel) + 1 == self.parties {
+			self.arrived.store(0, Ordering::Relaxed);
+			self.sense.store(local_sense, Ordering::Release);
+			if let WaitStrategy::SpinThenPark = self.strategy {
+				// Pega o mutex antes de notificar: quem ja conferiu a flag esta dentro do wait.
+				let _guard = self.parked.lock().expect("Mutex poisoned");
+				self.released.notify_all();
+			}
+			return;
+		}
+
+		match self.strategy {
+			WaitStrategy::Spin => spin_until(|| self.sense.load(Ordering::Acquire) == local_sense),
+			WaitStrategy::SpinThenPark => {
+				for _ in 0..SPIN_LIMIT {
+					if self.sense.load(Ordering::Acquire) == local_sense {
+						return;
+					}
+					hint::spin_loop();
+				}
+				let mut guard = self.parked.lock().expect("Mutex poisoned");
+				while self.sense.load(Ordering::Acquire) != local_sense {
+					guard = self.released.wait(guard).expect("Mutex poisoned");
+				}
+			}
+		}
+	}
+}
+
+struct TreeNode {
+	arrived: AtomicUsize,
+	parties: usize,
+	parent: Option<usize>,
+}
+
+// Arvore de combinacao: as chegadas sao contadas em nos de ate TREE_FAN_IN participantes, e so o
+// ultimo de cada no sobe para o pai, espalhando a disputa por varios contadores. O ultimo a chegar
+// na raiz inverte o sentido global que libera todos.
+pub struct CombiningTreeBarrier {
+	nodes: Box<[Padded<TreeNode>]>,
+	sense: AtomicBool,
+	local_sense: Box<[Padded<AtomicBool>]>,
+}
+
+impl CombiningTreeBarrier {
+	pub fn new(parties: usize) -> Self {
+		// Folhas primeiro: a thread i chega na folha i / TREE_FAN_IN.
+		let mut layout: Vec<(usize, Option<usize>)> = (0..parties)
+			.step_by(TREE_FAN_IN)
+			.map(|first| ((parties - first).min(TREE_FAN_IN), None))
+			.collect();
+		let mut level: Vec<usize> = (0..layout.len()).collect();
+		while level.len() > 1 {
+			let mut next = Vec::with_capacity(level.len().div_ceil(TREE_FAN_IN));
+			for group in level.chunks(TREE_FAN_IN) {
+				let parent = layout.len();
+				layout.push((group.len(), None));
+				for &child in group {
+					layout[child].1 = Some(parent);
+				}
+				next.push(parent);
+			}
+			level = next;
+		}
+
+		Self {
+			nodes: layout
+				.into_iter()
+				.map(|(parties, parent)| {
+					Padded(TreeNode {
+						arrived: AtomicUsize::new(0),
+						parties,
+						parent,
+					})
+				})
+				.collect(),
+			sense: AtomicBool::new(false),
+			local_sense: padded_slots(parties, || AtomicBool::new(false)),
+		}
+	}
+}
+
+impl PhaseBarrier for CombiningTreeBarrier {
+	fn wait(&self, thread_id: usize) {
+		let local_sense = !self.local_sense[thread_id].0.load(Ordering::Relaxed);
+		self.local_sense[thread_id].0.store(local_sense, Ordering::Relaxed);
+
+		let mut node = &self.nodes[thread_id / TREE_FAN_IN].0;
+		while node.arrived.fetch_add(1, Ordering::AcqRel) + 1 == node.parties {
+			node.arrived.store(0, Ordering::Relaxed);
+			match node.parent {
+				Some(parent) => node = &self.nodes[parent].0,
+				None => {
+					self.sense.store(local_sense, Ordering::Release);
+					return;
+				}
+			}
+		}
+
+		spin_until(|| self.sense.load(Ordering::Acquire) == local_sense);
+	}
+}
+
+// Disseminacao: na rodada k a thread i avisa a thread (i + 2^k) mod n e espera o aviso de
+// (i - 2^k) mod n. Apos ceil(log2 n) rodadas cada thread soube, direta ou indiretamente, de todas.
+// As flags guardam o numero do episodio, que so cresce, entao nao precisam ser limpas; cada flag
+// (thread, rodada) ocupa a propria linha de cache, na posicao thread * rounds + rodada.
+pub struct DisseminationBarrier {
+	parties: usize,
+	rounds: usize,
+	flags: Box<[Padded<AtomicUsize>]>,
+	episode: Box<[Padded<AtomicUsize>]>,
+}
+
+impl DisseminationBarrier {
+	pub fn new(parties: usize) -> Self {
+		let rounds = rounds_for(parties);
+		Self {
+			parties,
+			rounds,
+			flags: padded_slots(parties * rounds, || AtomicUsize::new(0)),
+			episode: padded_slots(parties, || AtomicUsize::new(0)),
+		}
+	}
+
+	fn flag(&self, thread_id: usize, round: usize) -> &AtomicUsize {
+		&self.flags[thread_id * self.rounds + round].0
+	}
+}
+
+impl PhaseBarrier for DisseminationBarrier {
+	fn wait(&self, thread_id: usize) {
+		let episode = self.episode[thread_id].0.load(Ordering::Relaxed) + 1;
+		self.episode[thread_id].0.store(episode, Ordering::Relaxed);
+
+		for round in 0..self.rounds {
+			let partner = (thread_id + (1 << round)) % self.parties;
+			self.flag(partner, round).store(episode, Ordering::Release);
+			let own = self.flag(thread_id, round);
+			spin_until(|| own.load(Ordering::Acquire) >= episode);
+		}
+	}
+}
+
+// Torneio: na rodada k a thread i com i multiplo de 2^(k+1) vence e espera a chegada de i + 2^k,
+// que perde e aguarda ser acordada. O campeao (thread 0) desce a arvore acordando quem venceu,
+// e cada um acorda os perdedores das rodadas que ganhou. Como na disseminacao, cada chegada
+// (vencedor, rodada) tem linha de cache propria.
+pub struct TournamentBarrier {
+	parties: usize,
+	rounds: usize,
+	arrivals: Box<[Padded<AtomicUsize>]>,
+	wakeups: Box<[Padded<AtomicUsize>]>,
+	episode: Box<[Padded<AtomicUsize>]>,
+}
+
+impl TournamentBarrier {
+	pub fn new(parties: usize) -> Self {
+		let rounds = rounds_for(parties);
+		Self {
+			parties,
+			rounds,
+			arrivals: padded_slots(parties * rounds, || AtomicUsize::new(0)),
+			wakeups: padded_slots(parties, || AtomicUsize::new(0)),
+			episode: padded_slots(parties, || AtomicUsize::new(0)),
+		}
+	}
+
+	fn arrival(&self, thread_id: usize, round: usize) -> &AtomicUsize {
+		&self.arrivals[thread_id * self.rounds + round].0
+	}
+}
+
+impl PhaseBarrier for TournamentBarrier {
+	fn wait(&self, thread_id: usize) {
+		let episode = self.episode[thread_id].0.load(Ordering::Relaxed) + 1;
+		self.episode[thread_id].0.store(episode, Ordering::Relaxed);
+
+		let mut round = 0;
+		while round < self.rounds {
+			let step = 1 << round;
+			if !thread_id.is_multiple_of(step * 2) {
+				self.arrival(thread_id - step, round).store(episode, Ordering::Release);
+				let wakeup = &self.wakeups[thread_id].0;
+				spin_until(|| wakeup.load(Ordering::Acquire) >= episode);
+				break;
+			}
+			if thread_id + step < self.parties {
+				let arrival = self.arrival(thread_id, round);
+				spin_until(|| arrival.load(Ordering::Acquire) >= episode);
+			}
+			round += 1;
+		}
+
+		for won in (0..round).rev() {
+			let loser = thread_id + (1 << won);
+			if loser < self.parties {
+				self.wakeups[loser].0.store(episode, Ordering::Release);
+			}
+		}
+	}
+}
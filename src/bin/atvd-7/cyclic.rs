@@ -0,0 +1,143 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Por que um wait nao completou, como as excecoes do CyclicBarrier do Java.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BarrierError {
+	// Esta thread desistiu de esperar; ao sair, ela quebra a barreira para as demais.
+	Timeout,
+	// Outro participante falhou (panico, timeout ou acao do lider com panico).
+	Broken,
+}
+
+impl BarrierError {
+	pub fn label(self) -> &'static str {
+		match self {
+			BarrierError::Timeout => "timeout",
+			BarrierError::Broken => "quebrada",
+		}
+	}
+}
+
+struct BarrierState {
+	arrived: usize,
+	generation: usize,
+	broken: bool,
+}
+
+type BarrierAction = Box<dyn Fn() + Send + Sync>;
+
+// Barreira ciclica que nunca deixa ninguem preso: uma falha de qualquer participante marca a
+// barreira como quebrada e libera todos os que esperam com `BarrierError::Broken`. Quebrada, ela
+// continua assim; todo wait seguinte falha na hora.
+pub struct CyclicBarrier {
+	parties: usize,
+	state: Mutex<BarrierState>,
+	released: Condvar,
+	action: Option<BarrierAction>,
+}
+
+impl CyclicBarrier {
+	pub fn new(parties: usize) -> Self {
+		assert!(parties > 0, "Use ao menos um participante");
+		Self {
+			parties,
+			state: Mutex::new(BarrierState {
+				arrived: 0,
+				generation: 0,
+				broken: false,
+			}),
+			released: Condvar::new(),
+			action: None,
+		}
+	}
+
+	// A acao roda no ultimo a chegar, antes de liberar os demais; se ela entrar em panico, a barreira quebra.
+	pub fn with_action(parties: usize, action: impl Fn() + Send + Sync + 'static) -> Self {
+		Self {
+			action: Some(Box::new(action)),
+			..Self::new(parties)
+		}
+	}
+
+	// Devolve Ok(true) para o lider do episodio (quem chegou por ultimo e rodou a acao).
+	pub fn wait(&self) -> Result<bool, BarrierError> {
+		self.wait_until(None)
+	}
+
+	pub fn wait_timeout(&self, timeout: Duration) -> Result<bool, BarrierError> {
+		self.wait_until(Some(Instant::now() + timeout))
+	}
+
+	pub fn break_barrier(&self) {
+		let mut state = self.state.lock().expect("Mutex poisoned");
+		self.break_locked(&mut state);
+	}
+
+	pub fn is_broken(&self) -> bool {
+		self.state.lock().expect("Mutex poisoned").broken
+	}
+
+	fn break_locked(&self, state: &mut BarrierState) {
+		state.broken = true;
+		self.released.notify_all();
+	}
+
+	fn wait_until(&self, deadline: Option<Instant>) -> Result<bool, BarrierError> {
+		let mut state = self.state.lock().expect("Mutex poisoned");
+		if state.broken {
+			return Err(BarrierError::Broken);
+		}
+
+		state.arrived += 1;
+		if state.arrived == self.parties {
+			if let Some(action) = &self.action {
+				// A acao roda com o mutex adquirido: nenhum participante sai antes dela terminar.
+				if panic::catch_unwind(AssertUnwindSafe(action)).is_err() {
+					self.break_locked(&mut state);
+					return Err(BarrierError::Broken);
+				}
+			}
+			state.arrived = 0;
+			state.generation += 1;
+			self.released.notify_all();
+			return Ok(true);
+		}
+
+		let generation = state.generation;
+		loop {
+			state = match deadline {
+				None => self.released.wait(state).expect("Mutex poisoned"),
+				Some(deadline) => {
+					let remaining = deadline.saturating_duration_since(Instant::now());
+					self.released.wait_timeout(state, remaining).expect("Mutex poisoned").0
+				}
+			};
+
+			if state.generation != generation {
+				return Ok(false);
+			}
+			if state.broken {
+				return Err(BarrierError::Broken);
+			}
+			if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+				self.break_locked(&mut state);
+				return Err(BarrierError::Timeout);
+			}
+		}
+	}
+}
+
+// Guarda de participante: se a thread sair da fase em panico, a barreira quebra em vez de
+// deixar os demais presos esperando uma chegada que nunca vem.
+pub struct BreakOnPanic<'a>(pub &'a CyclicBarrier);
+
+impl Drop for BreakOnPanic<'_> {
+	fn drop(&mut self) {
+		if thread::panicking() {
+			self.0.break_barrier();
+		}
+	}
+}
@@ -0,0 +1,124 @@
+use crate::phaser::Phaser;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+pub const PHASES: usize = 6;
+// Cada trabalhador entra em uma das tres primeiras fases e fica quatro fases: o numero de
+// participantes cresce ate a fase 3 e depois diminui.
+const JOIN_SPREAD: usize = 3;
+const LIFETIME: usize = 4;
+const PHASE_WORK: Duration = Duration::from_micros(100);
+
+// Fases (inclusive) em que o trabalhador participa.
+pub fn schedule(worker: usize) -> (usize, usize) {
+	let join = worker % JOIN_SPREAD;
+	(join, join + LIFETIME - 1)
+}
+
+pub struct PhaseReport {
+	pub registered: usize,
+	pub joined: usize,
+	pub left: usize,
+	pub arrivals: usize,
+	pub expected: usize,
+}
+
+pub struct DynamicRun {
+	pub phases: Vec<PhaseReport>,
+	pub violations: usize,
+}
+
+// O coordenador (thread principal) e uma parte desde o inicio: ele registra quem entra na fase
+// antes de chegar, entao a fase nao pode avancar sem os recem-chegados. Ao comecar a fase p + 1,
+// cada thread confere se todas as chegadas esperadas da fase p ja foram contadas e se o phaser
+// realmente esta na fase seguinte.
+pub fn run_dynamic_phases(workers: usize) -> DynamicRun {
+	let phaser = Arc::new(Phaser::new(1));
+	let arrivals: Arc<Vec<AtomicUsize>> = Arc::new((0..PHASES).map(|_| AtomicUsize::new(0)).collect());
+	// Esperado por fase: os trabalhadores ativos mais o coordenador.
+	let expected: Arc<Vec<usize>> = Arc::new(
+		(0..PHASES)
+			.map(|phase| {
+				1 + (0..workers)
+					.filter(|&worker| {
+						let (join, last) = schedule(worker);
+						(join..=last).contains(&phase)
+					})
+					.count()
+			})
+			.collect(),
+	);
+	let violations = Arc::new(AtomicUsize::new(0));
+	let mut handles = Vec::with_capacity(workers);
+	let mut reports = Vec::with_capacity(PHASES);
+
+	for phase in 0..PHASES {
+		let mut joined = 0;
+		for worker in (0..workers).filter(|&worker| schedule(worker).0 == phase) {
+			let registered_at = phaser.register();
+			if registered_at != phase {
+				violations.fetch_add(1, Ordering::SeqCst);
+			}
+			joined += 1;
+
+			let phaser_clone = Arc::clone(&phaser);
+			let arrivals_clone = Arc::clone(&arrivals);
+			let expected_clone = Arc::clone(&expected);
+			let violations_clone = Arc::clone(&violations);
+			handles.push(thread::spawn(move || {
+				let (join, last) = schedule(worker);
+				for current in join..=last {
+					thread::sleep(PHASE_WORK * (1 + worker as u32 % 3));
+					arrivals_clone[current].fetch_add(1, Ordering::SeqCst);
+					if current == last {
+						phaser_clone.arrive_and_deregister();
+						break;
+					}
+					let next = phaser_clone.arrive_and_await_advance();
+					if next != current + 1 || arrivals_clone[current].load(Ordering::SeqCst) != expected_clone[current] {
+						violations_clone.fetch_add(1, Ordering::SeqCst);
+					}
+				}
+			}));
+		}
+
+		// A fase nao avanca antes da chegada do coordenador, entao a leitura ainda e desta fase.
+		let registered = phaser.phase_parties();
+		let left = (0..workers).filter(|&worker| schedule(worker).1 == phase).count();
+		arrivals[phase].fetch_add(1, Ordering::SeqCst);
+		if phase + 1 == PHASES {
+			phaser.arrive_and_deregister();
+		} else {
+			let next = phaser.arrive_and_await_advance();
+			if next != phase + 1 || arrivals[phase].load(Ordering::SeqCst) != expected[phase] {
+				violations.fetch_add(1, Ordering::SeqCst);
+			}
+		}
+
+		reports.push(PhaseReport {
+			registered,
+			joined,
+			left,
+			arrivals: 0,
+			expected: expected[phase],
+		});
+	}
+
+	for handle in handles {
+		handle.join().expect("Thread panicked during execution");
+	}
+
+	for (phase, report) in reports.iter_mut().enumerate() {
+		report.arrivals = arrivals[phase].load(Ordering::SeqCst);
+		if report.arrivals != report.expected || report.registered != report.expected {
+			violations.fetch_add(1, Ordering::SeqCst);
+		}
+	}
+
+	DynamicRun {
+		phases: reports,
+		violations: violations.load(Ordering::SeqCst),
+	}
+}
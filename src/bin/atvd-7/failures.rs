@@ -0,0 +1,181 @@
+use crate::cyclic::{BarrierError, BreakOnPanic, CyclicBarrier};
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const PHASES: usize = 4;
+// Fase (base 0) em que a falha e injetada, sempre na ultima thread.
+pub const FAIL_AT_PHASE: usize = 1;
+const PHASE_WORK: Duration = Duration::from_micros(200);
+pub const WAIT_TIMEOUT: Duration = Duration::from_millis(100);
+// Bem maior que o timeout: sem ele, os demais ficariam esperando todo esse tempo (ou para sempre).
+const STALL: Duration = Duration::from_millis(500);
+const THREAD_PREFIX: &str = "falha-";
+const INJECTED_PANIC: &str = "Falha injetada antes da barreira";
+
+#[derive(Clone, Copy)]
+pub enum FailureScenario {
+	None,
+	Panic,
+	Stall,
+	LeaderActionPanic,
+}
+
+impl FailureScenario {
+	pub const ALL: [FailureScenario; 4] = [
+		FailureScenario::None,
+		FailureScenario::Panic,
+		FailureScenario::Stall,
+		FailureScenario::LeaderActionPanic,
+	];
+
+	pub fn label(self) -> &'static str {
+		match self {
+			FailureScenario::None => "sem falha",
+			FailureScenario::Panic => "panico de uma thread",
+			FailureScenario::Stall => "thread travada",
+			FailureScenario::LeaderActionPanic => "panico na acao do lider",
+		}
+	}
+}
+
+pub enum ThreadResult {
+	Completed,
+	Failed(BarrierError),
+	Panicked,
+}
+
+impl ThreadResult {
+	pub fn label(&self) -> &'static str {
+		match self {
+			ThreadResult::Completed => "concluiu",
+			ThreadResult::Failed(error) => error.label(),
+			ThreadResult::Panicked => "panico",
+		}
+	}
+}
+
+// Como cada thread terminou: fases que atravessou, resultado e quanto ficou no ultimo wait.
+pub struct ThreadOutcome {
+	pub phases_completed: usize,
+	pub result: ThreadResult,
+	pub last_wait: Duration,
+}
+
+pub struct ScenarioOutcome {
+	pub scenario: FailureScenario,
+	pub failing_thread: usize,
+	pub leader_actions: usize,
+	pub broken: bool,
+	pub elapsed: Duration,
+	pub threads: Vec<ThreadOutcome>,
+}
+
+pub fn run_scenarios(thread_count: usize) -> Vec<ScenarioOutcome> {
+	// Silencia apenas os panicos das threads desta demonstracao; os demais seguem para o hook padrao.
+	let default_hook = Arc::new(panic::take_hook());
+	let hook_fallback = Arc::clone(&default_hook);
+	panic::set_hook(Box::new(move |info| {
+		let is_demo = thread::current()
+			.name()
+			.is_some_and(|name| name.starts_with(THREAD_PREFIX));
+		if !is_demo {
+			hook_fallback(info);
+		}
+	}));
+
+	let outcomes = FailureScenario::ALL
+		.into_iter()
+		.map(|scenario| run_scenario(scenario, thread_count))
+		.collect();
+
+	drop(panic::take_hook());
+	let default_hook = Arc::try_unwrap(default_hook).unwrap_or_else(|_| panic!("Hook padrao ainda compartilhado"));
+	panic::set_hook(default_hook);
+
+	outcomes
+}
+
+fn run_scenario(scenario: FailureScenario, thread_count: usize) -> ScenarioOutcome {
+	let failing_thread = thread_count - 1;
+	let leader_actions = Arc::new(AtomicUsize::new(0));
+	let actions_clone = Arc::clone(&leader_actions);
+	let barrier = Arc::new(CyclicBarrier::with_action(thread_count, move || {
+		let completed = actions_clone.fetch_add(1, Ordering::SeqCst);
+		if let FailureScenario::LeaderActionPanic = scenario
+			&& completed == FAIL_AT_PHASE
+		{
+			panic!("{}", INJECTED_PANIC);
+		}
+	}));
+
+	let start = Instant::now();
+	let handles: Vec<_> = (0..thread_count)
+		.map(|id| {
+			let barrier_clone = Arc::clone(&barrier);
+			thread::Builder::new()
+				.name(format!("{}{}", THREAD_PREFIX, id))
+				.spawn(move || run_participant(&barrier_clone, scenario, id == failing_thread))
+				.expect("Falha ao criar thread")
+		})
+		.collect();
+
+	let threads = handles
+		.into_iter()
+		.map(|handle| {
+			handle.join().unwrap_or(ThreadOutcome {
+				phases_completed: FAIL_AT_PHASE,
+				result: ThreadResult::Panicked,
+				last_wait: Duration::ZERO,
+			})
+		})
+		.collect();
+
+	ScenarioOutcome {
+		scenario,
+		failing_thread,
+		leader_actions: leader_actions.load(Ordering::SeqCst),
+		broken: barrier.is_broken(),
+		elapsed: start.elapsed(),
+		threads,
+	}
+}
+
+fn run_participant(barrier: &CyclicBarrier, scenario: FailureScenario, is_failing: bool) -> ThreadOutcome {
+	let _guard = BreakOnPanic(barrier);
+	let mut last_wait = Duration::ZERO;
+
+	for phase in 0..PHASES {
+		thread::sleep(PHASE_WORK);
+		if is_failing && phase == FAIL_AT_PHASE {
+			match scenario {
+				FailureScenario::Panic => panic!("{}", INJECTED_PANIC),
+				FailureScenario::Stall => thread::sleep(STALL),
+				FailureScenario::None | FailureScenario::LeaderActionPanic => {}
+			}
+		}
+
+		let wait_start = Instant::now();
+		// So o cenario de travamento precisa de prazo; nos demais a quebra da barreira ja libera todos.
+		let result = match scenario {
+			FailureScenario::Stall => barrier.wait_timeout(WAIT_TIMEOUT),
+			_ => barrier.wait(),
+		};
+		last_wait = wait_start.elapsed();
+		if let Err(error) = result {
+			return ThreadOutcome {
+				phases_completed: phase,
+				result: ThreadResult::Failed(error),
+				last_wait,
+			};
+		}
+	}
+
+	ThreadOutcome {
+		phases_completed: PHASES,
+		result: ThreadResult::Completed,
+		last_wait,
+	}
+}
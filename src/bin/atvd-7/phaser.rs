@@ -0,0 +1,96 @@
+use std::sync::{Condvar, Mutex};
+
+struct PhaserState {
+	phase: usize,
+	parties: usize,
+	arrived: usize,
+	// Partes que sairam durante a fase corrente; zera quando a fase avanca.
+	departed: usize,
+	terminated: bool,
+}
+
+// Barreira com participantes dinamicos, no estilo do Phaser do Java: `register` inclui uma parte
+// na fase corrente, `arrive` conta a chegada sem esperar, `arrive_and_deregister` chega e sai, e
+// `await_advance` espera a fase informada terminar. A fase avanca quando todas as partes
+// registradas chegaram; quando a ultima parte sai, o phaser termina e libera qualquer espera.
+pub struct Phaser {
+	state: Mutex<PhaserState>,
+	advanced: Condvar,
+}
+
+impl Phaser {
+	pub fn new(parties: usize) -> Self {
+		Self {
+			state: Mutex::new(PhaserState {
+				phase: 0,
+				parties,
+				arrived: 0,
+				departed: 0,
+				terminated: false,
+			}),
+			advanced: Condvar::new(),
+		}
+	}
+
+	// Devolve a fase em que a nova parte passa a contar.
+	pub fn register(&self) -> usize {
+		let mut state = self.state.lock().expect("Mutex poisoned");
+		assert!(!state.terminated, "Phaser ja terminado");
+		state.parties += 1;
+		state.phase
+	}
+
+	// Devolve a fase em que a chegada foi contada.
+	pub fn arrive(&self) -> usize {
+		self.arrive_with(false)
+	}
+
+	pub fn arrive_and_deregister(&self) -> usize {
+		self.arrive_with(true)
+	}
+
+	// Espera a fase `phase` terminar e devolve a nova fase; se ela ja terminou, devolve na hora.
+	pub fn await_advance(&self, phase: usize) -> usize {
+		let mut state = self.state.lock().expect("Mutex poisoned");
+		while state.phase == phase && !state.terminated {
+			state = self.advanced.wait(state).expect("Mutex poisoned");
+		}
+		state.phase
+	}
+
+	pub fn arrive_and_await_advance(&self) -> usize {
+		let phase = self.arrive();
+		self.await_advance(phase)
+	}
+
+	// Partes da fase corrente, contando as que ja chegaram e sairam nela; diferente das partes
+	// ainda registradas, nao depende de quantas saidas aconteceram antes da leitura.
+	pub fn phase_parties(&self) -> usize {
+		let state = self.state.lock().expect("Mutex poisoned");
+		state.parties + state.departed
+	}
+
+	fn arrive_with(&self, deregister: bool) -> usize {
+		let mut state = self.state.lock().expect("Mutex poisoned");
+		assert!(state.arrived < state.parties, "Mais chegadas que partes registradas");
+		let phase = state.phase;
+
+		if deregister {
+			state.parties -= 1;
+			state.departed += 1;
+		} else {
+			state.arrived += 1;
+		}
+
+		if state.parties == 0 {
+			state.terminated = true;
+			self.advanced.notify_all();
+		} else if state.arrived == state.parties {
+			state.arrived = 0;
+			state.departed = 0;
+			state.phase += 1;
+			self.advanced.notify_all();
+		}
+		phase
+	}
+}
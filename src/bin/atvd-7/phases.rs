@@ -0,0 +1,154 @@
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Trabalho base de cada thread por fase, o mesmo da versao em duas fases.
+const BASE_WORK: Duration = Duration::from_micros(200);
+// Quantas vezes o retardatario demora mais que os demais.
+const STRAGGLER_FACTOR: u32 = 8;
+const STRAGGLER_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+// Como o trabalho de cada fase e dividido entre as threads.
+#[derive(Clone, Copy)]
+pub enum WorkDistribution {
+	// Todas as threads trabalham BASE_WORK.
+	Uniform,
+	// Rampa linear: a thread i trabalha 2 * BASE_WORK * (i + 1) / n, com a mesma media da uniforme.
+	Skewed,
+	// Em cada fase uma thread sorteada trabalha STRAGGLER_FACTOR vezes mais; as demais, BASE_WORK.
+	Straggler,
+}
+
+impl WorkDistribution {
+	pub const ALL: [WorkDistribution; 3] =
+		[WorkDistribution::Uniform, WorkDistribution::Skewed, WorkDistribution::Straggler];
+
+	pub fn parse(text: &str) -> Option<Self> {
+		match text {
+			"uniforme" => Some(WorkDistribution::Uniform),
+			"desbalanceada" => Some(WorkDistribution::Skewed),
+			"retardataria" => Some(WorkDistribution::Straggler),
+			_ => None,
+		}
+	}
+
+	pub fn label(self) -> &'static str {
+		match self {
+			WorkDistribution::Uniform => "uniforme",
+			WorkDistribution::Skewed => "desbalanceada",
+			WorkDistribution::Straggler => "retardataria",
+		}
+	}
+
+	// Trabalho planejado por fase e por thread: plan[fase][thread].
+	pub fn plan(self, thread_count: usize, phases: usize) -> Vec<Vec<Duration>> {
+		let mut generator = XorShift64::new(STRAGGLER_SEED);
+		(0..phases)
+			.map(|_| {
+				let straggler = (generator.next_u64() % thread_count as u64) as usize;
+				(0..thread_count)
+					.map(|id| match self {
+						WorkDistribution::Uniform => BASE_WORK,
+						WorkDistribution::Skewed => BASE_WORK * 2 * (id as u32 + 1) / thread_count as u32,
+						WorkDistribution::Straggler if id == straggler => BASE_WORK * STRAGGLER_FACTOR,
+						WorkDistribution::Straggler => BASE_WORK,
+					})
+					.collect()
+			})
+			.collect()
+	}
+}
+
+// O que uma thread fez em uma fase: trabalho util medido e tempo parado na barreira.
+#[derive(Clone, Copy)]
+pub struct PhaseSample {
+	pub work: Duration,
+	pub wait: Duration,
+}
+
+// Resultado por fase e por thread: samples[fase][thread].
+pub struct PhaseRun {
+	pub samples: Vec<Vec<PhaseSample>>,
+}
+
+impl PhaseRun {
+	pub fn phase_work(&self, phase: usize) -> Duration {
+		self.samples[phase].iter().map(|sample| sample.work).sum()
+	}
+
+	pub fn phase_wait(&self, phase: usize) -> Duration {
+		self.samples[phase].iter().map(|sample| sample.wait).sum()
+	}
+
+	pub fn total_work(&self) -> Duration {
+		(0..self.samples.len()).map(|phase| self.phase_work(phase)).sum()
+	}
+
+	pub fn total_wait(&self) -> Duration {
+		(0..self.samples.len()).map(|phase| self.phase_wait(phase)).sum()
+	}
+
+	// Maior espera de uma unica thread na fase: quanto o mais rapido ficou parado pelo mais lento.
+	pub fn max_wait(&self, phase: usize) -> Duration {
+		self.samples[phase].iter().map(|sample| sample.wait).max().unwrap_or_default()
+	}
+}
+
+// K fases separadas por barreira; cada thread dorme o trabalho planejado e mede quanto espera em cada wait.
+pub fn barrier_phases(plan: &[Vec<Duration>]) -> PhaseRun {
+	let phases = plan.len();
+	let thread_count = plan.first().map_or(0, Vec::len);
+	let barrier = Arc::new(Barrier::new(thread_count));
+	let plan = Arc::new(plan.to_vec());
+	let mut handles = Vec::with_capacity(thread_count);
+
+	for id in 0..thread_count {
+		let barrier_clone = Arc::clone(&barrier);
+		let plan_clone = Arc::clone(&plan);
+		handles.push(thread::spawn(move || {
+			let mut samples = Vec::with_capacity(phases);
+			for phase_plan in plan_clone.iter() {
+				let work_start = Instant::now();
+				thread::sleep(phase_plan[id]);
+				let wait_start = Instant::now();
+				barrier_clone.wait();
+				samples.push(PhaseSample {
+					work: wait_start - work_start,
+					wait: wait_start.elapsed(),
+				});
+			}
+			samples
+		}));
+	}
+
+	let per_thread: Vec<Vec<PhaseSample>> = handles
+		.into_iter()
+		.map(|handle| handle.join().expect("Thread panicked during execution"))
+		.collect();
+
+	PhaseRun {
+		samples: (0..phases)
+			.map(|phase| per_thread.iter().map(|samples| samples[phase]).collect())
+			.collect(),
+	}
+}
+
+struct XorShift64 {
+	state: u64,
+}
+
+impl XorShift64 {
+	fn new(seed: u64) -> Self {
+		let state = if seed == 0 { 0xA511_E9B7_C3D2_1234 } else { seed };
+		Self { state }
+	}
+
+	fn next_u64(&mut self) -> u64 {
+		let mut x = self.state;
+		x ^= x << 13;
+		x ^= x >> 7;
+		x ^= x << 17;
+		self.state = x;
+		x
+	}
+}
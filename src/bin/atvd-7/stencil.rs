@@ -0,0 +1,173 @@
+use std::sync::{Arc, Barrier, Mutex};
+use std::thread;
+
+// Temperatura fixa da borda superior; as demais bordas e o interior comecam em 0.
+const HOT_EDGE: f64 = 100.0;
+
+// Placa 2D rows x cols (bordas incluidas) aquecida pela borda superior. Cada iteracao de Jacobi
+// troca cada ponto interior pela media dos quatro vizinhos da iteracao anterior; o solver para
+// em `max_iterations` ou quando a maior variacao de um ponto fica abaixo de `threshold`.
+#[derive(Clone, Copy)]
+pub struct HeatProblem {
+	pub rows: usize,
+	pub cols: usize,
+	pub max_iterations: usize,
+	pub threshold: f64,
+}
+
+pub struct HeatResult {
+	pub grid: Vec<f64>,
+	pub iterations: usize,
+	pub delta: f64,
+}
+
+impl HeatProblem {
+	fn initial_grid(&self) -> Vec<f64> {
+		let mut grid = vec![0.0; self.rows * self.cols];
+		grid[..self.cols].fill(HOT_EDGE);
+		grid
+	}
+
+	fn interior_rows(&self) -> usize {
+		self.rows - 2
+	}
+}
+
+// Atualiza as linhas interiores `rows` de `next` a partir de `current` (mesma largura) e devolve
+// a maior variacao. A expressao e a mesma nas duas versoes, o que garante resultado bit a bit igual.
+fn relax_rows(current: &[f64], next: &mut [f64], cols: usize, rows: std::ops::Range<usize>) -> f64 {
+	let mut delta = 0.0f64;
+	for row in rows {
+		for col in 1..cols - 1 {
+			let index = row * cols + col;
+			let value = 0.25 * (current[index - cols] + current[index + cols] + current[index - 1] + current[index + 1]);
+			delta = delta.max((value - current[index]).abs());
+			next[index] = value;
+		}
+	}
+	delta
+}
+
+pub fn solve_sequential(problem: &HeatProblem) -> HeatResult {
+	let mut current = problem.initial_grid();
+	let mut next = current.clone();
+	let mut iterations = 0;
+	let mut delta = f64::INFINITY;
+
+	while iterations < problem.max_iterations && delta >= problem.threshold {
+		delta = relax_rows(&current, &mut next, problem.cols, 1..problem.rows - 1);
+		std::mem::swap(&mut current, &mut next);
+		iterations += 1;
+	}
+
+	HeatResult {
+		grid: current,
+		iterations,
+		delta,
+	}
+}
+
+// Linhas de fronteira e variacao local que cada thread publica por iteracao.
+struct Halo {
+	top: Mutex<Vec<f64>>,
+	bottom: Mutex<Vec<f64>>,
+	delta: Mutex<f64>,
+}
+
+impl Halo {
+	fn new(cols: usize) -> Self {
+		Self {
+			top: Mutex::new(vec![0.0; cols]),
+			bottom: Mutex::new(vec![0.0; cols]),
+			delta: Mutex::new(0.0),
+		}
+	}
+}
+
+// Cada thread e dona de uma faixa de linhas e guarda uma copia local com uma linha fantasma acima
+// e abaixo. Por iteracao: calcula a faixa, publica as linhas de fronteira e a variacao local,
+// espera na barreira, copia as fronteiras das vizinhas para as fantasmas e reduz a variacao global
+// (todas leem os mesmos valores, entao decidem parar juntas). As publicacoes alternam entre dois
+// conjuntos pela paridade da iteracao, o que dispensa uma segunda barreira antes de sobrescreve-las.
+pub fn solve_parallel(problem: &HeatProblem, thread_count: usize) -> HeatResult {
+	let threads = thread_count.clamp(1, problem.interior_rows());
+	let cols = problem.cols;
+	let initial = Arc::new(problem.initial_grid());
+	let halos: Arc<[Vec<Halo>; 2]> = Arc::new([
+		(0..threads).map(|_| Halo::new(cols)).collect(),
+		(0..threads).map(|_| Halo::new(cols)).collect(),
+	]);
+	let barrier = Arc::new(Barrier::new(threads));
+	let problem = *problem;
+
+	let handles: Vec<_> = (0..threads)
+		.map(|id| {
+			let initial = Arc::clone(&initial);
+			let halos = Arc::clone(&halos);
+			let barrier = Arc::clone(&barrier);
+			thread::spawn(move || {
+				let first = 1 + id * problem.interior_rows() / threads;
+				let end = 1 + (id + 1) * problem.interior_rows() / threads;
+				let strip = end - first;
+				// Linhas first - 1 ..= end da grade global, incluindo as fantasmas.
+				let mut current = initial[(first - 1) * cols..(end + 1) * cols].to_vec();
+				let mut next = current.clone();
+				let mut iterations = 0;
+				let mut delta = f64::INFINITY;
+
+				while iterations < problem.max_iterations && delta >= problem.threshold {
+					let local_delta = relax_rows(&current, &mut next, cols, 1..strip + 1);
+
+					let own = &halos[iterations % 2];
+					own[id].top.lock().expect("Mutex poisoned").copy_from_slice(&next[cols..2 * cols]);
+					own[id]
+						.bottom
+						.lock()
+						.expect("Mutex poisoned")
+						.copy_from_slice(&next[strip * cols..(strip + 1) * cols]);
+					*own[id].delta.lock().expect("Mutex poisoned") = local_delta;
+
+					barrier.wait();
+
+					// As faixas das pontas mantem a borda fixa da placa como fantasma.
+					if id > 0 {
+						next[..cols].copy_from_slice(&own[id - 1].bottom.lock().expect("Mutex poisoned"));
+					}
+					if id + 1 < threads {
+						next[(strip + 1) * cols..].copy_from_slice(&own[id + 1].top.lock().expect("Mutex poisoned"));
+					}
+					delta = own
+						.iter()
+						.map(|halo| *halo.delta.lock().expect("Mutex poisoned"))
+						.fold(0.0, f64::max);
+
+					std::mem::swap(&mut current, &mut next);
+					iterations += 1;
+				}
+
+				(first, current[cols..(strip + 1) * cols].to_vec(), iterations, delta)
+			})
+		})
+		.collect();
+
+	let mut grid = initial.to_vec();
+	let mut iterations = 0;
+	let mut delta = 0.0;
+	for handle in handles {
+		let (first, rows, thread_iterations, thread_delta) = handle.join().expect("Thread panicked during execution");
+		grid[first * cols..first * cols + rows.len()].copy_from_slice(&rows);
+		iterations = thread_iterations;
+		delta = thread_delta;
+	}
+
+	HeatResult {
+		grid,
+		iterations,
+		delta,
+	}
+}
+
+// Compara pelos bits, nao por tolerancia: a versao paralela deve refazer exatamente as mesmas contas.
+pub fn bitwise_equal(left: &[f64], right: &[f64]) -> bool {
+	left.len() == right.len() && left.iter().zip(right).all(|(a, b)| a.to_bits() == b.to_bits())
+}
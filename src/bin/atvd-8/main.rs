@@ -8,33 +8,56 @@ use std::time::{Duration, Instant};
 
 const RUNS: usize = 5;
 const DEFAULT_TOTAL_ITEMS: usize = 200;
-const PRODUCER_COUNT: usize = 2;
-const CONSUMER_COUNT: usize = 2;
-const QUEUE_CAPACITY: usize = 32;
+const DEFAULT_PRODUCER_COUNT: usize = 2;
+const DEFAULT_CONSUMER_COUNT: usize = 2;
+const DEFAULT_QUEUE_CAPACITY: usize = 32;
+const DEFAULT_ITEM_WORK_MICROS: u64 = 150;
 const SENTINEL: i32 = -1;
+// Modo varredura: todas as combinacoes de produtores x consumidores x capacidade x trabalho por item.
+const SWEEP_PRODUCERS: [usize; 3] = [1, 2, 4];
+const SWEEP_CONSUMERS: [usize; 3] = [1, 2, 4];
+const SWEEP_CAPACITIES: [usize; 3] = [1, 8, 64];
+const SWEEP_WORK_MICROS: [u64; 3] = [0, 50, 150];
+// Acima desta fracao do tempo com produtores parados no send, a fila cheia e o gargalo.
+const BLOCKED_BOTTLENECK: f64 = 0.5;
 
 fn main() {
-	assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful");
+	const { assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful") };
 
 	let total_items = read_total_items().unwrap_or_else(|err| {
 		eprintln!("{}", err);
 		std::process::exit(1);
 	});
 
-	assert!(total_items >= PRODUCER_COUNT, "Quantidade total deve ser >= numero de produtores");
+	let mode = read_mode().unwrap_or_else(|err| {
+		eprintln!("{}", err);
+		std::process::exit(1);
+	});
+
+	let topology = match mode {
+		Mode::Measure(topology) => topology,
+		Mode::Sweep => {
+			run_sweep(total_items);
+			return;
+		}
+	};
+
+	assert!(total_items >= topology.producers, "Quantidade total deve ser >= numero de produtores");
 
 	println!("Atividade 8 — Produtor-Consumidor com fila bloqueante");
 	println!(
-		"Threads: {} produtores, {} consumidores; total previsto: {} itens",
-		PRODUCER_COUNT,
-		CONSUMER_COUNT,
+		"Threads: {} produtores, {} consumidores; capacidade da fila: {}; trabalho por item: {} us; total previsto: {} itens",
+		topology.producers,
+		topology.consumers,
+		topology.capacity,
+		topology.work.as_micros(),
 		total_items
 	);
 	println!("Total de execucoes temporizadas: {} ({} entram na media)", RUNS, RUNS - 1);
 
 	println!("\nLogs da execucao de aquecimento (run 1):");
 
-	let (avg_time, durations, results) = measure_runs(|run| run_producer_consumer(total_items, run == 0));
+	let (avg_time, durations, results) = measure_runs(|run| run_producer_consumer(total_items, &topology, run == 0));
 
 	println!("\nTempos com fila bloqueante (ms):");
 	log_durations(&durations);
@@ -47,11 +70,24 @@ fn main() {
 		println!(
 			"  Sentinelas consumidos: {} (esperado {})",
 			final_result.sentinels,
-			CONSUMER_COUNT
+			topology.consumers
 		);
 		println!("  Deadlock detectado: {}", final_result.deadlock_detected);
 	}
 
+	let measured = &results[1..];
+	println!("\nMedias apos aquecimento:");
+	println!("  Vazao: {:.1} itens/s", total_items as f64 / avg_time);
+	println!(
+		"  Latencia media por item (envio ate fim do processamento): {:.3} ms",
+		measured.iter().map(|result| result.mean_latency(total_items)).sum::<f64>() / measured.len() as f64 * 1_000.0
+	);
+	println!(
+		"  Tempo de produtores bloqueados no send: {:.3} ms (soma dos produtores)",
+		measured.iter().map(|result| result.producer_blocked.as_secs_f64()).sum::<f64>() / measured.len() as f64
+			* 1_000.0
+	);
+
 	println!("Conclusao: fila bloqueante coordena produtores e consumidores sem travamentos quando os sentinelas encerram cada consumidor.");
 }
 
@@ -80,6 +116,55 @@ fn read_total_items() -> Result<usize, String> {
 		.map_err(|_| format!("Entrada invalida para total de itens: {}", trimmed))
 }
 
+#[derive(Clone, Copy)]
+struct Topology {
+	producers: usize,
+	consumers: usize,
+	capacity: usize,
+	work: Duration,
+}
+
+enum Mode {
+	Measure(Topology),
+	Sweep,
+}
+
+// Apos o total de itens: `varredura`, ou produtores, consumidores, capacidade e trabalho por item (us),
+// cada um opcional e com o valor padrao quando ausente.
+fn read_mode() -> Result<Mode, String> {
+	if env::args().nth(2).as_deref() == Some("varredura") {
+		return Ok(Mode::Sweep);
+	}
+
+	let producers = read_positive_arg(2, "produtores", DEFAULT_PRODUCER_COUNT)?;
+	let consumers = read_positive_arg(3, "consumidores", DEFAULT_CONSUMER_COUNT)?;
+	let capacity = read_positive_arg(4, "capacidade da fila", DEFAULT_QUEUE_CAPACITY)?;
+	let work_micros = match env::args().nth(5) {
+		Some(arg) => arg
+			.parse::<u64>()
+			.map_err(|_| format!("Argumento invalido para trabalho por item (us): {}", arg))?,
+		None => DEFAULT_ITEM_WORK_MICROS,
+	};
+
+	Ok(Mode::Measure(Topology {
+		producers,
+		consumers,
+		capacity,
+		work: Duration::from_micros(work_micros),
+	}))
+}
+
+fn read_positive_arg(position: usize, name: &str, default: usize) -> Result<usize, String> {
+	match env::args().nth(position) {
+		Some(arg) => arg
+			.parse::<usize>()
+			.ok()
+			.filter(|&value| value > 0)
+			.ok_or_else(|| format!("Argumento invalido para {}: {}", name, arg)),
+		None => Ok(default),
+	}
+}
+
 fn measure_runs<F, T>(mut job: F) -> (f64, Vec<Duration>, Vec<T>)
 where
 	F: FnMut(usize) -> T,
@@ -119,10 +204,20 @@ struct ProducerConsumerResult {
 	consumed: usize,
 	sentinels: usize,
 	deadlock_detected: bool,
+	// Soma, sobre os itens, do envio ate o fim do processamento.
+	total_latency: Duration,
+	// Soma, sobre os produtores, do tempo parado no send com a fila cheia.
+	producer_blocked: Duration,
+}
+
+impl ProducerConsumerResult {
+	fn mean_latency(&self, total_items: usize) -> f64 {
+		self.total_latency.as_secs_f64() / total_items.max(1) as f64
+	}
 }
 
-fn run_producer_consumer(total_items: usize, should_log: bool) -> ProducerConsumerResult {
-	let (tx, rx) = mpsc::sync_channel::<i32>(QUEUE_CAPACITY);
+fn run_producer_consumer(total_items: usize, topology: &Topology, should_log: bool) -> ProducerConsumerResult {
+	let (tx, rx) = mpsc::sync_channel::<(i32, Instant)>(topology.capacity);
 	let shared_rx = Arc::new(Mutex::new(rx));
 
 	let produced_count = Arc::new(AtomicUsize::new(0));
@@ -131,19 +226,22 @@ fn run_producer_consumer(total_items: usize, should_log: bool) -> ProducerConsum
 
 	let mut producer_handles = Vec::new();
 
-	let base_items = total_items / PRODUCER_COUNT;
-	let remainder = total_items % PRODUCER_COUNT;
+	let base_items = total_items / topology.producers;
+	let remainder = total_items % topology.producers;
 
-	for producer_id in 0..PRODUCER_COUNT {
+	for producer_id in 0..topology.producers {
 		let producer_tx = tx.clone();
 		let produced_clone = Arc::clone(&produced_count);
 		let items_to_produce = base_items + if producer_id < remainder { 1 } else { 0 };
 		producer_handles.push(thread::spawn(move || {
+			let mut blocked = Duration::ZERO;
 			for item_idx in 0..items_to_produce {
 				let item = (producer_id * 10_000 + item_idx) as i32;
+				let send_start = Instant::now();
 				producer_tx
-					.send(item)
+					.send((item, send_start))
 					.expect("Erro ao enviar item para a fila");
+				blocked += send_start.elapsed();
 				produced_clone.fetch_add(1, Ordering::SeqCst);
 				if should_log && item_idx < 5 {
 					println!("Produtor {} enviou item {}", producer_id, item);
@@ -156,65 +254,139 @@ fn run_producer_consumer(total_items: usize, should_log: bool) -> ProducerConsum
 					items_to_produce
 				);
 			}
+			blocked
 		}));
 	}
 
 	let mut consumer_handles = Vec::new();
-	for consumer_id in 0..CONSUMER_COUNT {
+	for consumer_id in 0..topology.consumers {
 		let rx_clone = Arc::clone(&shared_rx);
 		let consumed_clone = Arc::clone(&consumed_count);
 		let sentinel_clone = Arc::clone(&sentinel_count);
-		consumer_handles.push(thread::spawn(move || loop {
-			let message = {
-				let receiver_guard = rx_clone
-					.lock()
-					.expect("Falha ao adquirir lock do receiver");
-				receiver_guard.recv()
-			};
-
-			match message {
-				Ok(SENTINEL) => {
-					sentinel_clone.fetch_add(1, Ordering::SeqCst);
-					if should_log {
-						println!("Consumidor {} recebeu sentinela", consumer_id);
+		let work = topology.work;
+		consumer_handles.push(thread::spawn(move || {
+			let mut latency = Duration::ZERO;
+			loop {
+				let message = {
+					let receiver_guard = rx_clone
+						.lock()
+						.expect("Falha ao adquirir lock do receiver");
+					receiver_guard.recv()
+				};
+
+				match message {
+					Ok((SENTINEL, _)) => {
+						sentinel_clone.fetch_add(1, Ordering::SeqCst);
+						if should_log {
+							println!("Consumidor {} recebeu sentinela", consumer_id);
+						}
+						break;
 					}
-					break;
-				}
-				Ok(item) => {
-					let current = consumed_clone.fetch_add(1, Ordering::SeqCst) + 1;
-					if should_log && current <= 5 {
-						println!("Consumidor {} processou item {}", consumer_id, item);
+					Ok((item, sent_at)) => {
+						let current = consumed_clone.fetch_add(1, Ordering::SeqCst) + 1;
+						if should_log && current <= 5 {
+							println!("Consumidor {} processou item {}", consumer_id, item);
+						}
+						if !work.is_zero() {
+							thread::sleep(work);
+						}
+						latency += sent_at.elapsed();
 					}
-					thread::sleep(Duration::from_micros(150));
+					Err(_) => break,
 				}
-				Err(_) => break,
 			}
+			latency
 		}));
 	}
 
-	for handle in producer_handles {
-		handle.join().expect("Produtor panicked durante execucao");
-	}
+	let producer_blocked = producer_handles
+		.into_iter()
+		.map(|handle| handle.join().expect("Produtor panicked durante execucao"))
+		.sum();
 
-	for _ in 0..CONSUMER_COUNT {
-		tx.send(SENTINEL)
+	for _ in 0..topology.consumers {
+		tx.send((SENTINEL, Instant::now()))
 			.expect("Falha ao enviar sentinela ao consumidor");
 	}
 	drop(tx);
 
-	for handle in consumer_handles {
-		handle.join().expect("Consumidor panicked durante execucao");
-	}
+	let total_latency = consumer_handles
+		.into_iter()
+		.map(|handle| handle.join().expect("Consumidor panicked durante execucao"))
+		.sum();
 
 	let produced = produced_count.load(Ordering::SeqCst);
 	let consumed = consumed_count.load(Ordering::SeqCst);
 	let sentinels = sentinel_count.load(Ordering::SeqCst);
-	let deadlock_detected = produced != total_items || consumed != total_items || sentinels != CONSUMER_COUNT;
+	let deadlock_detected = produced != total_items || consumed != total_items || sentinels != topology.consumers;
 
 	ProducerConsumerResult {
 		produced,
 		consumed,
 		sentinels,
 		deadlock_detected,
+		total_latency,
+		producer_blocked,
 	}
-}
\ No newline at end of file
+}
+
+fn run_sweep(total_items: usize) {
+	println!("Atividade 8 — Varredura da topologia produtor-consumidor");
+	println!(
+		"{} itens por execucao; produtores {:?} x consumidores {:?} x capacidade {:?} x trabalho por item (us) {:?}",
+		total_items,
+		SWEEP_PRODUCERS,
+		SWEEP_CONSUMERS,
+		SWEEP_CAPACITIES,
+		SWEEP_WORK_MICROS
+	);
+	println!("Total de execucoes temporizadas: {} ({} entram na media)", RUNS, RUNS - 1);
+
+	println!(
+		"\nProd | Cons | Capacidade | Trabalho (us) | Vazao (itens/s) | Latencia media (ms) | Produtores bloqueados (%) | Corretude"
+	);
+	for work_micros in SWEEP_WORK_MICROS {
+		for producers in SWEEP_PRODUCERS {
+			for consumers in SWEEP_CONSUMERS {
+				for capacity in SWEEP_CAPACITIES {
+					let topology = Topology {
+						producers,
+						consumers,
+						capacity,
+						work: Duration::from_micros(work_micros),
+					};
+					let (avg, _, results) = measure_runs(|_| run_producer_consumer(total_items, &topology, false));
+					let measured = &results[1..];
+					let latency =
+						measured.iter().map(|result| result.mean_latency(total_items)).sum::<f64>() / measured.len() as f64;
+					let blocked = measured
+						.iter()
+						.map(|result| result.producer_blocked.as_secs_f64())
+						.sum::<f64>()
+						/ measured.len() as f64;
+					// Fracao do tempo total em que cada produtor, em media, ficou parado no send.
+					let blocked_share = blocked / (avg * producers as f64);
+					println!(
+						"{:>4} | {:>4} | {:>10} | {:>13} | {:>15.1} | {:>19.3} | {:>24.1}% | {}{}",
+						producers,
+						consumers,
+						capacity,
+						work_micros,
+						total_items as f64 / avg,
+						latency * 1_000.0,
+						blocked_share * 100.0,
+						if results.iter().all(|result| !result.deadlock_detected) { "OK" } else { "FALHOU" },
+						if blocked_share > BLOCKED_BOTTLENECK { "  <- fila cheia" } else { "" }
+					);
+				}
+			}
+		}
+	}
+	println!(
+		"Leitura: quando os consumidores sao mais lentos que os produtores, a fila enche e os produtores passam a maior \
+	parte do tempo parados no send; aumentar a capacidade so adia esse ponto e aumenta a latencia, pois cada item espera \
+	atras de mais itens. A vazao so melhora com mais consumidores (ou menos trabalho por item). Sem trabalho, o mutex \
+	do receiver compartilhado e a propria fila viram o limite. Em um unico nucleo o tempo no send inclui a espera \
+	pela CPU, o que infla a fracao bloqueada."
+	);
+}
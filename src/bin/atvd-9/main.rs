@@ -1,3 +1,6 @@
+mod placement;
+
+use placement::{report_placement, ThreadPlacement};
 use std::env;
 use std::io::{self, Write};
 use std::sync::Arc;
@@ -9,7 +12,7 @@ const DEFAULT_VECTOR_LEN: usize = 20_000_000;
 const THREAD_COUNTS: [usize; 4] = [1, 2, 4, 8];
 
 fn main() {
-	assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful");
+	const { assert!(RUNS >= 3, "Use at least three runs to keep statistics meaningful") };
 
 	let vector_len = read_vector_len().unwrap_or_else(|err| {
 		eprintln!("{}", err);
@@ -35,13 +38,16 @@ fn main() {
 
 	for &threads in &THREAD_COUNTS {
 		let (avg, durations, outputs) =
-			measure_runs(|run| parallel_sum(&data, threads, run == 0));
+			measure_runs(|run| parallel_sum(&data, threads, run == 0, false));
 
 		println!("\nTempos com {} thread(s) (ms):", threads);
 		log_durations(&durations);
 		println!("Tempo medio (ms): {:.6}", avg * 1_000.0);
 
-		let correct = outputs.iter().skip(1).all(|&sum| sum == sequential_result);
+		let correct = outputs.iter().skip(1).all(|run| run.total == sequential_result);
+		// Execucao extra fora da medicao, so para amostrar os nucleos.
+		println!("Posicionamento das threads (execucao extra fora da medicao):");
+		report_placement(&parallel_sum(&data, threads, false, true).placements);
 		stats.push(ParallelStats {
 			threads,
 			avg_seconds: avg,
@@ -155,17 +161,40 @@ fn sequential_sum(data: &Arc<Vec<i64>>, should_log: bool) -> i64 {
 	data.iter().copied().sum()
 }
 
-fn parallel_sum(data: &Arc<Vec<i64>>, threads: usize, should_log: bool) -> i64 {
+struct SumRun {
+	total: i64,
+	placements: Vec<ThreadPlacement>,
+}
+
+// Sem `sample_placement` e a soma simples das medicoes; com ele, a execucao extra de posicionamento
+// soma em duas metades para amostrar o nucleo no meio do trabalho.
+fn sum_chunk(slice: &[i64], chunk_idx: usize, sample_placement: bool) -> (i64, Option<ThreadPlacement>) {
+	if !sample_placement {
+		return (slice.iter().copied().sum(), None);
+	}
+	let mut placement = ThreadPlacement::start(chunk_idx);
+	let (first_half, second_half) = slice.split_at(slice.len() / 2);
+	let mut partial = first_half.iter().copied().sum::<i64>();
+	placement.mark_midway();
+	partial += second_half.iter().copied().sum::<i64>();
+	(partial, Some(placement.finish()))
+}
+
+fn parallel_sum(data: &Arc<Vec<i64>>, threads: usize, should_log: bool, sample_placement: bool) -> SumRun {
 	let len = data.len();
 	let actual_threads = threads.min(len.max(1));
 	if should_log {
 		println!("Soma paralela com {} thread(s) para {} elementos", actual_threads, len);
 	}
 	if actual_threads <= 1 {
-		return data.iter().copied().sum();
+		let (total, placement) = sum_chunk(data, 0, sample_placement);
+		return SumRun {
+			total,
+			placements: placement.into_iter().collect(),
+		};
 	}
 
-	let chunk_size = (len + actual_threads - 1) / actual_threads;
+	let chunk_size = len.div_ceil(actual_threads);
 	let mut handles = Vec::with_capacity(actual_threads);
 
 	for chunk_idx in 0..actual_threads {
@@ -175,12 +204,16 @@ fn parallel_sum(data: &Arc<Vec<i64>>, threads: usize, should_log: bool) -> i64 {
 		}
 		let end = (start + chunk_size).min(len);
 		let data_clone = Arc::clone(data);
-		handles.push(thread::spawn(move || data_clone[start..end].iter().copied().sum::<i64>()));
+		handles.push(thread::spawn(move || sum_chunk(&data_clone[start..end], chunk_idx, sample_placement)));
 	}
 
 	let mut total = 0_i64;
+	let mut placements = Vec::with_capacity(handles.len());
 	for handle in handles {
-		total += handle.join().expect("Thread panicked durante o map-reduce");
+		let (partial, placement) = handle.join().expect("Thread panicked durante o map-reduce");
+		total += partial;
+		placements.extend(placement);
 	}
-	total
+
+	SumRun { total, placements }
 }
\ No newline at end of file